@@ -1,13 +1,14 @@
+use futures_util::StreamExt;
 use openai_rst::{
     assistant::AssistantRequest,
     client::Client,
     common::MessageRole,
-    message::CreateMessageRequest,
+    message::{Content, CreateMessageRequest},
     models::{Model, GPT4},
-    run::CreateRunRequest,
+    run::{CreateRunRequest, RunStreamEvent},
     thread::CreateThreadRequest,
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, pin::pin};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -42,27 +43,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("{:?}", message_result.id.clone());
 
     let run_req = CreateRunRequest::new(result.id);
-    let run_result = client.create_run(thread_result.id.clone(), run_req).await?;
+    let events = client
+        .create_run_stream(thread_result.id.clone(), run_req)
+        .await?;
+    let mut events = pin!(events);
 
-    loop {
-        let run_result = client
-            .retrieve_run(thread_result.id.clone(), run_result.id.clone())
-            .await?;
-        if run_result.status == "completed" {
-            break;
-        } else {
-            println!("waiting...");
-            std::thread::sleep(std::time::Duration::from_secs(1));
+    while let Some(event) = events.next().await {
+        match event? {
+            RunStreamEvent::MessageDelta(delta) => {
+                for content in delta.delta.content {
+                    if let Some(value) = content.text.and_then(|text| text.value) {
+                        print!("{}", value);
+                    }
+                }
+            }
+            RunStreamEvent::RunCompleted(_) => {
+                println!();
+                break;
+            }
+            _ => {}
         }
     }
 
-    let list_message_result = client.list_messages(thread_result.id.clone()).await?;
+    let list_message_result = client
+        .list_messages(thread_result.id.clone(), None, None, None, None, None)
+        .await?;
     for data in list_message_result.data {
         for content in data.content {
-            println!(
-                "{:?}: {:?} {:?}",
-                data.role, content.text.value, content.text.annotations
-            );
+            match content {
+                Content::Text { text } => {
+                    println!("{:?}: {:?} {:?}", data.role, text.value, text.annotations);
+                }
+                Content::ImageFile { image_file } => {
+                    println!("{:?}: image file {:?}", data.role, image_file.file_id);
+                }
+            }
         }
     }
 
@@ -8,11 +8,11 @@ use openai_rst::{
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = Client::from_env().unwrap();
 
-    let mut req = EmbeddingRequest::new(
-        Model::Embedding(EmbeddingsModels::TextEmbeddingAda002),
+    let req = EmbeddingRequest::new(
+        Model::Embedding(EmbeddingsModels::TextEmbedding3Small),
         "story time".to_string(),
-    );
-    req.dimensions = Some(10);
+    )
+    .dimensions(10);
 
     let result = client.embedding(req).await?;
     println!("{:?}", result.data);
@@ -27,11 +27,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut properties = HashMap::new();
     properties.insert(
         "coin".to_string(),
-        Box::new(JSONSchemaDefine {
-            schema_type: Some(JSONSchemaType::String),
-            description: Some("The cryptocurrency to get the price of".to_string()),
-            ..Default::default()
-        }),
+        Box::new(JSONSchemaDefine::string(
+            "The cryptocurrency to get the price of",
+        )),
     );
 
     let req = ChatCompletionRequest::new_multi(
@@ -40,6 +38,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             role: MessageRole::User,
             content: Content::Text(String::from("What is the price of Ethereum?")),
             name: None,
+            tool_call_id: None,
+            tool_calls: None,
         }],
     )
     .tools(vec![Tool {
@@ -94,6 +94,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(FinishReason::null) => {
             println!("Null");
         }
+        Some(FinishReason::unknown) => {
+            println!("Unknown");
+        }
     }
     Ok(())
 }
@@ -0,0 +1,86 @@
+use openai_rst::{
+    chat_completion::{
+        ChatCompletionRequest, FinishReason, Function, FunctionParameters,
+        JSONSchemaDefine, JSONSchemaType, Tool, ToolChoiceType, ToolType,
+    },
+    client::Client,
+    models::{Model, GPT4},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Currency {
+    coin: String,
+}
+
+fn get_coin_price(coin: &str) -> f64 {
+    match coin.to_lowercase().as_str() {
+        "btc" | "bitcoin" => 10000.0,
+        "eth" | "ethereum" => 1000.0,
+        _ => 0.0,
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = Client::from_env().unwrap();
+
+    let mut properties = HashMap::new();
+    properties.insert(
+        "coin".to_string(),
+        Box::new(JSONSchemaDefine::string(
+            "The cryptocurrency to get the price of",
+        )),
+    );
+
+    let mut req = ChatCompletionRequest::new(
+        Model::GPT4(GPT4::GPT4o),
+        "What are the prices of Bitcoin and Ethereum, and what is their sum?".into(),
+    )
+    .tools(vec![Tool {
+        r#type: ToolType::Function,
+        function: Function {
+            name: String::from("get_coin_price"),
+            description: Some(String::from("Get the price of a cryptocurrency")),
+            parameters: FunctionParameters {
+                schema_type: JSONSchemaType::Object,
+                properties: Some(properties),
+                required: Some(vec![String::from("coin")]),
+            },
+        },
+    }])
+    .tool_choice(ToolChoiceType::Auto);
+
+    loop {
+        let response = client.chat_completion(req.clone()).await?;
+        let choice = &response.choices[0];
+        match choice.finish_reason {
+            Some(FinishReason::tool_calls) => {
+                let mut results = Vec::new();
+                for tool_call in choice.message.tool_calls.iter().flatten() {
+                    let content = match tool_call.function.name.as_deref() {
+                        Some("get_coin_price") => {
+                            let args: Currency = tool_call.function.parse_arguments()?;
+                            let price = get_coin_price(&args.coin);
+                            println!("get_coin_price({}) = {}", args.coin, price);
+                            price.to_string()
+                        }
+                        name => format!("unknown function {:?}", name),
+                    };
+                    results.push((tool_call.id.clone(), content));
+                }
+                req.continue_with_tool_results(&response, results);
+            }
+            Some(FinishReason::stop) => {
+                println!("{}", choice.message.content.clone().unwrap_or_default());
+                break;
+            }
+            ref other => {
+                println!("Stopped early: {:?}", other);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
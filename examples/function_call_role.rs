@@ -38,6 +38,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "What is the price of Ethereum?",
             )),
             name: None,
+            tool_call_id: None,
+            tool_calls: None,
         }],
     )
     .tools(vec![chat_completion::Tool {
@@ -92,6 +94,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 "What is the price of Ethereum?",
                             )),
                             name: None,
+                            tool_call_id: None,
+                            tool_calls: None,
                         },
                         chat_completion::ChatCompletionMessage {
                             role: MessageRole::Function,
@@ -100,6 +104,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 format!("{{\"price\": {}}}", price)
                             }),
                             name: Some(String::from("get_coin_price")),
+                            tool_call_id: None,
+                            tool_calls: None,
                         },
                     ],
                 );
@@ -114,6 +120,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(chat_completion::FinishReason::null) => {
             println!("Null");
         }
+        Some(chat_completion::FinishReason::unknown) => {
+            println!("Unknown");
+        }
     }
     Ok(())
 }
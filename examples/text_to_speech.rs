@@ -1,4 +1,4 @@
-use openai_rst::audio::{self, AudioSpeechRequest, TTS_1};
+use openai_rst::audio::{AudioSpeechRequest, TtsModel, Voice};
 use openai_rst::client::Client;
 
 #[tokio::main]
@@ -6,9 +6,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = Client::from_env().unwrap();
 
     let req = AudioSpeechRequest::new(
-        TTS_1.to_string(),
+        TtsModel::Tts1,
         String::from("Money is not problem, Problem is no money"),
-        audio::VOICE_ALLOY.to_string(),
+        Voice::Alloy,
         String::from("problem.mp3"),
     );
 
@@ -0,0 +1,27 @@
+use openai_rst::{
+    client::Client,
+    embedding::EmbeddingRequest,
+    models::{EmbeddingsModels, Model},
+};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new("openai_rst=debug"))
+        .init();
+
+    let client = Client::from_env()?;
+
+    let req = EmbeddingRequest::new(
+        Model::Embedding(EmbeddingsModels::TextEmbedding3Small),
+        "story time".to_string(),
+    );
+
+    let result = client.embedding(req).await?;
+    println!("{:?}", result.usage);
+
+    Ok(())
+}
+
+// OPENAI_API_KEY=xxxx cargo run --package openai-rst --example tracing
@@ -18,6 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     r#type: chat_completion::ContentType::text,
                     text: Some(String::from("What’s in this image?")),
                     image_url: None,
+                    input_audio: None,
                 },
                 chat_completion::ImageUrl {
                     r#type: chat_completion::ContentType::image_url,
@@ -27,9 +28,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             "https://upload.wikimedia.org/wikipedia/commons/5/50/Bitcoin.png",
                         ),
                     }),
+                    input_audio: None,
                 },
             ]),
             name: None,
+            tool_call_id: None,
+            tool_calls: None,
         }],
     );
 
@@ -78,7 +78,8 @@ pub struct AssistantObject {
     pub instructions: Option<String>,
     /// Tools associated with the assistant.
     pub tools: Vec<HashMap<String, String>>,
-    /// File IDs associated with the assistant.
+    /// File IDs associated with the assistant, absent from `assistants=v2` responses.
+    #[serde(default)]
     pub file_ids: Vec<String>,
     /// Metadata for the assistant.
     pub metadata: HashMap<String, String>,
@@ -99,6 +100,18 @@ pub struct DeletionStatus {
     pub headers: Option<HashMap<String, String>>,
 }
 
+impl DeletionStatus {
+    /// Creates a successful `DeletionStatus` for the given ID and object type.
+    pub fn deleted(id: String, object: &str) -> Self {
+        Self {
+            id,
+            object: object.to_owned(),
+            deleted: true,
+            headers: None,
+        }
+    }
+}
+
 /// Represents a list of assistants.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ListAssistant {
@@ -142,3 +155,32 @@ pub struct ListAssistantFile {
     /// Optional headers associated with the list of assistant files.
     pub headers: Option<HashMap<String, String>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_v2_assistant_without_file_ids() {
+        let assistant: AssistantObject = serde_json::from_str(
+            r#"{
+                "id": "asst_abc123",
+                "object": "assistant",
+                "created_at": 1698984975,
+                "name": "Math Tutor",
+                "description": null,
+                "model": "gpt-4o",
+                "instructions": "You are a personal math tutor.",
+                "tools": [{"type": "code_interpreter"}],
+                "tool_resources": {"code_interpreter": {"file_ids": []}},
+                "metadata": {},
+                "top_p": 1.0,
+                "temperature": 1.0,
+                "response_format": "auto"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(assistant.id, "asst_abc123");
+        assert!(assistant.file_ids.is_empty());
+    }
+}
@@ -6,19 +6,48 @@
 //! Features include:
 //! - AudioTranscriptionRequest: Handles requests for audio file transcription using specified models.
 //! - AudioTranscriptionResponse: Returns the transcription text along with optional headers.
+//! - TranscriptionSegment: Timed segment returned by `verbose_json` transcriptions.
+//! - WordTimestamp: Per-word timing returned when `word` granularity is requested.
 //! - AudioTranslationRequest: Manages requests for translating audio content using specific models.
 //! - AudioTranslationResponse: Delivers translated text and optional response headers.
 //! - AudioSpeechRequest: Manages requests for generating speech from text using designated voice models.
+//! - TtsModel / Voice: Enums of the text-to-speech models and voices, mapping to their wire names.
 //! - AudioSpeechResponse: Provides the success status of the speech synthesis operation and optional headers.
+//! - check_audio_size: Checks an audio file against the upload limit before it is sent.
 //!
 //! Constants for model and voice identifiers are also defined to standardize the references used across requests.
 
-use crate::impl_builder_methods;
+use crate::{error::APIError, file::check_file_size, impl_builder_methods};
+use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
+use strum::{AsRefStr, Display, EnumString};
 
 pub const WHISPER_1: &str = "whisper-1";
 
+/// Maximum size in bytes of an audio file accepted by the transcription endpoint.
+pub const MAX_AUDIO_FILE_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Checks that the audio file at `path` fits within `MAX_AUDIO_FILE_BYTES`, so oversized
+/// recordings fail fast with a clear error instead of an opaque upload failure.
+///
+/// Longer recordings must be split into smaller files and transcribed one by one.
+pub async fn check_audio_size(path: &str) -> Result<(), APIError> {
+    check_file_size(path, MAX_AUDIO_FILE_BYTES).await
+}
+
+/// Reads the audio file at `path` into a multipart part named after the file, after checking
+/// it against `MAX_AUDIO_FILE_BYTES`.
+async fn audio_part(path: &str) -> Result<Part, APIError> {
+    check_audio_size(path).await?;
+    let bytes = async_std::fs::read(path).await?;
+    let filename = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_owned());
+    Ok(Part::bytes(bytes).file_name(filename))
+}
+
 /// Represents a request for audio transcription.
 #[derive(Debug, Serialize, Clone)]
 pub struct AudioTranscriptionRequest {
@@ -38,6 +67,9 @@ pub struct AudioTranscriptionRequest {
     /// Optional language of the audio file.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
+    /// Optional timestamp granularities (`word` and/or `segment`), requires `verbose_json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_granularities: Option<Vec<String>>,
 }
 
 impl AudioTranscriptionRequest {
@@ -50,7 +82,38 @@ impl AudioTranscriptionRequest {
             response_format: None,
             temperature: None,
             language: None,
+            timestamp_granularities: None,
+        }
+    }
+
+    /// Builds the multipart form for this request, reading the audio file from disk.
+    ///
+    /// Returns an error without reading the file if it exceeds `MAX_AUDIO_FILE_BYTES`.
+    pub async fn form(&self) -> Result<Form, APIError> {
+        Ok(self.fields(Form::new().part("file", audio_part(&self.file).await?)))
+    }
+
+    /// Appends the non-file fields of this request to the given form.
+    fn fields(&self, mut form: Form) -> Form {
+        form = form.text("model", self.model.clone());
+        if let Some(prompt) = &self.prompt {
+            form = form.text("prompt", prompt.clone());
+        }
+        if let Some(response_format) = &self.response_format {
+            form = form.text("response_format", response_format.clone());
+        }
+        if let Some(temperature) = self.temperature {
+            form = form.text("temperature", temperature.to_string());
+        }
+        if let Some(language) = &self.language {
+            form = form.text("language", language.clone());
         }
+        if let Some(granularities) = &self.timestamp_granularities {
+            for granularity in granularities {
+                form = form.text("timestamp_granularities[]", granularity.clone());
+            }
+        }
+        form
     }
 }
 
@@ -59,7 +122,8 @@ impl_builder_methods!(
     prompt: String,
     response_format: String,
     temperature: f32,
-    language: String
+    language: String,
+    timestamp_granularities: Vec<String>
 );
 
 /// Represents the response from an audio transcription request.
@@ -67,10 +131,44 @@ impl_builder_methods!(
 pub struct AudioTranscriptionResponse {
     /// Transcribed text from the audio file.
     pub text: String,
+    /// Optional detected language of the audio file, returned with `verbose_json`.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Optional duration of the audio file in seconds, returned with `verbose_json`.
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Optional transcribed segments, returned with `verbose_json`.
+    #[serde(default)]
+    pub segments: Option<Vec<TranscriptionSegment>>,
+    /// Optional per-word timestamps, returned when `word` granularity is requested.
+    #[serde(default)]
+    pub words: Option<Vec<WordTimestamp>>,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
 
+/// Represents a timed segment of a `verbose_json` transcription.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TranscriptionSegment {
+    /// Start time of the segment in seconds.
+    pub start: f64,
+    /// End time of the segment in seconds.
+    pub end: f64,
+    /// Transcribed text of the segment.
+    pub text: String,
+}
+
+/// Represents the timing of a single transcribed word.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WordTimestamp {
+    /// Transcribed word.
+    pub word: String,
+    /// Start time of the word in seconds.
+    pub start: f64,
+    /// End time of the word in seconds.
+    pub end: f64,
+}
+
 /// Represents a request for audio translation.
 #[derive(Debug, Serialize, Clone)]
 pub struct AudioTranslationRequest {
@@ -100,6 +198,25 @@ impl AudioTranslationRequest {
             temperature: None,
         }
     }
+
+    /// Builds the multipart form for this request, reading the audio file from disk.
+    ///
+    /// Returns an error without reading the file if it exceeds `MAX_AUDIO_FILE_BYTES`.
+    pub async fn form(&self) -> Result<Form, APIError> {
+        let mut form = Form::new()
+            .part("file", audio_part(&self.file).await?)
+            .text("model", self.model.clone());
+        if let Some(prompt) = &self.prompt {
+            form = form.text("prompt", prompt.clone());
+        }
+        if let Some(response_format) = &self.response_format {
+            form = form.text("response_format", response_format.clone());
+        }
+        if let Some(temperature) = self.temperature {
+            form = form.text("temperature", temperature.to_string());
+        }
+        Ok(form)
+    }
 }
 
 impl_builder_methods!(
@@ -136,6 +253,86 @@ pub const VOICE_NOVA: &str = "nova";
 /// Constant for the Shimmer voice model.
 pub const VOICE_SHIMMER: &str = "shimmer";
 
+/// Enum representing the text-to-speech models.
+#[derive(
+    EnumString,
+    Debug,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Display,
+)]
+pub enum TtsModel {
+    /// Standard text-to-speech model, optimized for speed.
+    #[strum(serialize = "tts-1")]
+    #[serde(rename = "tts-1")]
+    Tts1,
+
+    /// High-definition text-to-speech model, optimized for quality.
+    #[strum(serialize = "tts-1-hd")]
+    #[serde(rename = "tts-1-hd")]
+    Tts1Hd,
+
+    /// Text-to-speech model built on GPT-4o mini, steerable through instructions.
+    #[strum(serialize = "gpt-4o-mini-tts")]
+    #[serde(rename = "gpt-4o-mini-tts")]
+    Gpt4oMiniTts,
+}
+
+impl From<TtsModel> for String {
+    /// Converts a `TtsModel` into its wire name.
+    fn from(model: TtsModel) -> Self {
+        model.to_string()
+    }
+}
+
+/// Enum representing the voices available for speech synthesis.
+#[derive(
+    EnumString,
+    Debug,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Display,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Voice {
+    /// The Alloy voice.
+    Alloy,
+    /// The Ash voice.
+    Ash,
+    /// The Coral voice.
+    Coral,
+    /// The Echo voice.
+    Echo,
+    /// The Fable voice.
+    Fable,
+    /// The Onyx voice.
+    Onyx,
+    /// The Nova voice.
+    Nova,
+    /// The Sage voice.
+    Sage,
+    /// The Shimmer voice.
+    Shimmer,
+}
+
+impl From<Voice> for String {
+    /// Converts a `Voice` into its wire name.
+    fn from(voice: Voice) -> Self {
+        voice.to_string()
+    }
+}
+
 /// Represents a request for text-to-speech synthesis.
 #[derive(Debug, Serialize, Clone)]
 pub struct AudioSpeechRequest {
@@ -151,11 +348,18 @@ pub struct AudioSpeechRequest {
 
 impl AudioSpeechRequest {
     /// Creates a new `AudioSpeechRequest` with the specified model, input, voice, and output format.
-    pub fn new(model: String, input: String, voice: String, output: String) -> Self {
+    ///
+    /// The model and voice accept either the `TtsModel` and `Voice` enums or raw strings.
+    pub fn new(
+        model: impl Into<String>,
+        input: String,
+        voice: impl Into<String>,
+        output: String,
+    ) -> Self {
         Self {
-            model,
+            model: model.into(),
             input,
-            voice,
+            voice: voice.into(),
             output,
         }
     }
@@ -169,3 +373,121 @@ pub struct AudioSpeechResponse {
     /// Indicates whether the synthesis was successful.
     pub result: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_verbose_json_transcription() {
+        let response: AudioTranscriptionResponse = serde_json::from_str(
+            r#"{
+                "task": "transcribe",
+                "language": "english",
+                "duration": 8.47,
+                "text": "The beach was a popular spot.",
+                "segments": [{
+                    "id": 0,
+                    "seek": 0,
+                    "start": 0.0,
+                    "end": 3.32,
+                    "text": "The beach was a popular spot.",
+                    "tokens": [50364, 440, 7534],
+                    "temperature": 0.0,
+                    "avg_logprob": -0.28,
+                    "compression_ratio": 1.24,
+                    "no_speech_prob": 0.01
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(response.language.as_deref(), Some("english"));
+        assert_eq!(response.duration, Some(8.47));
+        let segments = response.segments.unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].end, 3.32);
+        assert_eq!(segments[0].text, "The beach was a popular spot.");
+    }
+
+    #[test]
+    fn deserializes_plain_json_transcription() {
+        let response: AudioTranscriptionResponse =
+            serde_json::from_str(r#"{"text": "Hello"}"#).unwrap();
+        assert_eq!(response.text, "Hello");
+        assert!(response.language.is_none());
+        assert!(response.duration.is_none());
+        assert!(response.segments.is_none());
+    }
+
+    #[test]
+    fn deserializes_word_timestamps() {
+        let response: AudioTranscriptionResponse = serde_json::from_str(
+            r#"{
+                "task": "transcribe",
+                "language": "english",
+                "duration": 1.2,
+                "text": "Hello world",
+                "words": [
+                    {"word": "Hello", "start": 0.0, "end": 0.5},
+                    {"word": "world", "start": 0.6, "end": 1.1}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let words = response.words.unwrap();
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[1].word, "world");
+        assert_eq!(words[1].start, 0.6);
+    }
+
+    #[tokio::test]
+    async fn check_audio_size_rejects_oversized_file() {
+        let path = std::env::temp_dir().join("openai-rst-oversized.mp3");
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_len(MAX_AUDIO_FILE_BYTES).unwrap();
+        let path_str = path.to_string_lossy().into_owned();
+        assert!(check_audio_size(&path_str).await.is_ok());
+
+        file.set_len(MAX_AUDIO_FILE_BYTES + 1).unwrap();
+        let result = check_audio_size(&path_str).await;
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Err(APIError::Unknown(err)) => assert!(
+                err.contains(&format!("{} bytes", MAX_AUDIO_FILE_BYTES + 1)),
+                "{}",
+                err
+            ),
+            other => panic!("expected an oversized file error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn tts_models_and_voices_round_trip() {
+        for (model, wire) in [
+            (TtsModel::Tts1, "tts-1"),
+            (TtsModel::Tts1Hd, "tts-1-hd"),
+            (TtsModel::Gpt4oMiniTts, "gpt-4o-mini-tts"),
+        ] {
+            let json = serde_json::to_string(&model).unwrap();
+            assert_eq!(json, format!("\"{}\"", wire));
+            assert_eq!(serde_json::from_str::<TtsModel>(&json).unwrap(), model);
+            assert_eq!(String::from(model), wire);
+        }
+        for (voice, wire) in [
+            (Voice::Alloy, "alloy"),
+            (Voice::Ash, "ash"),
+            (Voice::Coral, "coral"),
+            (Voice::Echo, "echo"),
+            (Voice::Fable, "fable"),
+            (Voice::Onyx, "onyx"),
+            (Voice::Nova, "nova"),
+            (Voice::Sage, "sage"),
+            (Voice::Shimmer, "shimmer"),
+        ] {
+            let json = serde_json::to_string(&voice).unwrap();
+            assert_eq!(json, format!("\"{}\"", wire));
+            assert_eq!(serde_json::from_str::<Voice>(&json).unwrap(), voice);
+            assert_eq!(String::from(voice), wire);
+        }
+    }
+}
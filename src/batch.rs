@@ -0,0 +1,150 @@
+//! This module defines the structures for the Batch API, which runs a file of requests
+//! asynchronously at a reduced cost.
+//! It includes:
+//! - `CreateBatchRequest`: Struct for creating a new batch from an uploaded input file.
+//! - `BatchObject`: Struct representing a batch and its progress.
+//! - `BatchErrors` / `BatchError`: Structs describing validation errors of a batch's input file.
+//! - `BatchRequestCounts`: Struct tracking how many requests of a batch have completed or failed.
+//! - `ListBatch`: Struct for a page of batches.
+//! - `impl_builder_methods!`: Macro for generating builder methods for structs.
+//!
+//! The input file is uploaded through the file module with `FilePurpose::Batch`, and the
+//! `output_file_id` and `error_file_id` of a finished batch are read back with
+//! `Client::file_retrieve_content`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::impl_builder_methods;
+
+/// Default time frame within which a batch is processed.
+const DEFAULT_COMPLETION_WINDOW: &str = "24h";
+
+/// Represents a request to create a new batch.
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateBatchRequest {
+    /// ID of an uploaded JSONL file containing the requests of the batch.
+    pub input_file_id: String,
+    /// Endpoint the requests are sent to, such as `/v1/chat/completions`.
+    pub endpoint: String,
+    /// Time frame within which the batch should be processed, currently only `24h`.
+    pub completion_window: String,
+    /// Optional key-value pairs attached to the batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl CreateBatchRequest {
+    /// Creates a new `CreateBatchRequest` for the given input file and endpoint, processed
+    /// within the default `24h` window.
+    pub fn new(input_file_id: String, endpoint: String) -> Self {
+        Self {
+            input_file_id,
+            endpoint,
+            completion_window: DEFAULT_COMPLETION_WINDOW.to_owned(),
+            metadata: None,
+        }
+    }
+}
+
+impl_builder_methods!(
+    CreateBatchRequest,
+    metadata: HashMap<String, String>
+);
+
+/// Represents a batch and its progress.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchObject {
+    /// Unique identifier for the batch.
+    pub id: String,
+    /// Object type, typically "batch".
+    pub object: String,
+    /// Endpoint the requests of the batch are sent to.
+    pub endpoint: String,
+    /// Optional validation errors of the input file.
+    pub errors: Option<BatchErrors>,
+    /// ID of the input file of the batch.
+    pub input_file_id: String,
+    /// Time frame within which the batch should be processed.
+    pub completion_window: String,
+    /// Status of the batch, such as `validating`, `in_progress` or `completed`.
+    pub status: String,
+    /// Optional ID of the file containing the successful responses.
+    pub output_file_id: Option<String>,
+    /// Optional ID of the file containing the failed requests.
+    pub error_file_id: Option<String>,
+    /// Timestamp of when the batch was created.
+    pub created_at: i64,
+    /// Optional timestamp of when the batch started processing.
+    pub in_progress_at: Option<i64>,
+    /// Optional timestamp of when the batch expires.
+    pub expires_at: Option<i64>,
+    /// Optional timestamp of when the batch started finalizing.
+    pub finalizing_at: Option<i64>,
+    /// Optional timestamp of when the batch completed.
+    pub completed_at: Option<i64>,
+    /// Optional timestamp of when the batch failed.
+    pub failed_at: Option<i64>,
+    /// Optional timestamp of when the batch expired.
+    pub expired_at: Option<i64>,
+    /// Optional timestamp of when the batch started cancelling.
+    pub cancelling_at: Option<i64>,
+    /// Optional timestamp of when the batch was cancelled.
+    pub cancelled_at: Option<i64>,
+    /// Optional counts of the requests of the batch by outcome.
+    pub request_counts: Option<BatchRequestCounts>,
+    /// Optional key-value pairs attached to the batch.
+    pub metadata: Option<HashMap<String, String>>,
+    /// Optional headers from the response.
+    pub headers: Option<HashMap<String, String>>,
+}
+
+/// Represents the validation errors of a batch's input file.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchErrors {
+    /// Object type, typically "list".
+    pub object: String,
+    /// List of errors.
+    pub data: Vec<BatchError>,
+}
+
+/// Represents a single validation error of a batch's input file.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchError {
+    /// Machine-readable error code.
+    pub code: Option<String>,
+    /// Human-readable description of the error.
+    pub message: Option<String>,
+    /// Optional name of the parameter that caused the error.
+    pub param: Option<String>,
+    /// Optional line of the input file that caused the error.
+    pub line: Option<i64>,
+}
+
+/// Represents how many requests of a batch have completed or failed.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchRequestCounts {
+    /// Total number of requests in the batch.
+    pub total: i64,
+    /// Number of requests that completed successfully.
+    pub completed: i64,
+    /// Number of requests that failed.
+    pub failed: i64,
+}
+
+/// Represents a page of batches.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListBatch {
+    /// Object type, typically "list".
+    pub object: String,
+    /// List of batch objects.
+    pub data: Vec<BatchObject>,
+    /// Optional ID of the first batch in the page.
+    pub first_id: Option<String>,
+    /// Optional ID of the last batch in the page.
+    pub last_id: Option<String>,
+    /// Indicates if there are more batches available.
+    pub has_more: bool,
+    /// Optional headers from the response.
+    pub headers: Option<HashMap<String, String>>,
+}
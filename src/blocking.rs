@@ -0,0 +1,377 @@
+//! This module provides a blocking facade over the asynchronous `Client`.
+//! It includes:
+//! - `Client`: Struct exposing the same typed methods as the async client, run to completion
+//!   on an internal Tokio runtime.
+//!
+//! The facade reuses the request and response types of the async API, so code can move between
+//! the two by adding or removing `.await`.
+
+use crate::{
+    assistant::{
+        AssistantFileObject, AssistantFileRequest, AssistantObject, AssistantRequest,
+        DeletionStatus, ListAssistant, ListAssistantFile,
+    },
+    audio::{
+        AudioSpeechRequest, AudioSpeechResponse, AudioTranscriptionRequest,
+        AudioTranscriptionResponse, AudioTranslationRequest, AudioTranslationResponse,
+    },
+    batch::{BatchObject, CreateBatchRequest, ListBatch},
+    chat_completion::{
+        ChatCompletionMessage, ChatCompletionRequest, ChatCompletionResponse,
+    },
+    client::{self, ApiResponse, RequestOptions},
+    completion::{CompletionRequest, CompletionResponse},
+    edit::{EditRequest, EditResponse},
+    embedding::{EmbeddingRequest, EmbeddingResponse},
+    error::APIError,
+    file::{
+        FileDeleteRequest, FileDeleteResponse, FileListResponse, FilePurpose,
+        FileRetrieveContentRequest, FileRetrieveContentResponse, FileRetrieveRequest,
+        FileRetrieveResponse, FileUploadRequest, FileUploadResponse,
+    },
+    fine_tuning::{
+        CancelFineTuningJobRequest, CreateFineTuningJobRequest, FineTuningCheckpoint,
+        FineTuningJobEvent, FineTuningJobObject, FineTuningPagination,
+        ListFineTuningJobEventsRequest, RetrieveFineTuningJobRequest,
+    },
+    image::{
+        ImageEditRequest, ImageEditResponse, ImageGenerationRequest,
+        ImageGenerationResponse, ImageVariationRequest, ImageVariationResponse,
+    },
+    message::{
+        CreateMessageRequest, ListMessage, ListMessageFile, MessageFileObject,
+        MessageObject, ModifyMessageRequest,
+    },
+    moderation::{CreateModerationRequest, CreateModerationResponse},
+    responses::{CreateResponseRequest, ResponseObject},
+    run::{
+        CreateRunRequest, CreateThreadAndRunRequest, ListRun, ListRunStep,
+        ModifyRunRequest, RunObject, RunStepObject,
+    },
+    thread::{CreateThreadRequest, ModifyThreadRequest, ThreadObject},
+    vector_store::{
+        CreateVectorStoreFileRequest, CreateVectorStoreRequest, ListVectorStore,
+        VectorStoreFileObject, VectorStoreObject,
+    },
+};
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+
+/// Result type alias for blocking client operations.
+type ClientResult<T> = Result<T, APIError>;
+
+/// Blocking counterpart of the async `Client`, for use outside of an async runtime.
+pub struct Client {
+    /// Async client performing the requests.
+    pub inner: client::Client,
+    /// Runtime the async requests are driven on.
+    runtime: Runtime,
+}
+
+impl Client {
+    /// Creates a new blocking `Client` instance from environment variables.
+    pub fn from_env() -> ClientResult<Self> {
+        Self::from_async(client::Client::from_env()?)
+    }
+
+    /// Creates a new blocking `Client` instance with the given API key.
+    pub fn new(api_key: String) -> ClientResult<Self> {
+        Self::from_async(client::Client::new(api_key)?)
+    }
+
+    /// Wraps an existing async `Client` in a blocking facade.
+    pub fn from_async(inner: client::Client) -> ClientResult<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { inner, runtime })
+    }
+}
+
+/// Generates blocking wrappers that drive the async method of the same name to completion.
+macro_rules! impl_blocking_methods {
+    ($($(#[$doc:meta])* fn $name:ident($($arg:ident: $arg_type:ty),* $(,)?) -> $ret:ty;)*) => {
+        impl Client {
+            $(
+                $(#[$doc])*
+                pub fn $name(&self, $($arg: $arg_type),*) -> ClientResult<$ret> {
+                    self.runtime.block_on(self.inner.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+impl_blocking_methods! {
+    /// Verifies the API key and connectivity with a cheap `GET /models` request.
+    fn verify_key() -> bool;
+    /// Sends a completion request and returns the response.
+    fn completion(req: CompletionRequest) -> CompletionResponse;
+    /// Sends a completion request and returns the response with its metadata.
+    fn completion_meta(req: CompletionRequest) -> ApiResponse<CompletionResponse>;
+    /// Sends an edit request and returns the response.
+    fn edit(req: EditRequest) -> EditResponse;
+    /// Runs an edit request as a chat completion and returns the result as an edit response.
+    fn edit_via_chat(req: EditRequest) -> EditResponse;
+    /// Sends an image generation request and returns the response.
+    fn image_generation(req: ImageGenerationRequest) -> ImageGenerationResponse;
+    /// Sends an image edit request and returns the response.
+    fn image_edit(req: ImageEditRequest) -> ImageEditResponse;
+    /// Sends an image variation request and returns the response.
+    fn image_variation(req: ImageVariationRequest) -> ImageVariationResponse;
+    /// Sends an embedding request and returns the response.
+    fn embedding(req: EmbeddingRequest) -> EmbeddingResponse;
+    /// Sends an embedding request and returns the response with its metadata.
+    fn embedding_meta(req: EmbeddingRequest) -> ApiResponse<EmbeddingResponse>;
+    /// Retrieves a list of files.
+    fn file_list() -> FileListResponse;
+    /// Retrieves a list of files, optionally narrowed to a single purpose.
+    fn file_list_filtered(purpose: Option<FilePurpose>) -> FileListResponse;
+    /// Retrieves a page of files, optionally narrowed to a single purpose.
+    fn file_list_page(
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        purpose: Option<FilePurpose>,
+    ) -> FileListResponse;
+    /// Uploads a file and returns the response.
+    fn file_upload(req: FileUploadRequest) -> FileUploadResponse;
+    /// Uploads in-memory data as a file named `filename`, without writing it to disk first.
+    fn file_upload_bytes(
+        bytes: Vec<u8>,
+        filename: String,
+        purpose: FilePurpose,
+    ) -> FileUploadResponse;
+    /// Deletes a file and returns the response.
+    fn file_delete(req: FileDeleteRequest) -> FileDeleteResponse;
+    /// Retrieves a file's metadata and returns the response.
+    fn file_retrieve(req: FileRetrieveRequest) -> FileRetrieveResponse;
+    /// Retrieves the content of a file and returns the response.
+    fn file_retrieve_content(
+        req: FileRetrieveContentRequest,
+    ) -> FileRetrieveContentResponse;
+    /// Sends a chat completion request and returns the response.
+    fn chat_completion(req: ChatCompletionRequest) -> ChatCompletionResponse;
+    /// Sends a chat completion request with per-request overrides and returns the response.
+    fn chat_completion_with_opts(
+        req: ChatCompletionRequest,
+        opts: &RequestOptions,
+    ) -> ChatCompletionResponse;
+    /// Sends a chat completion request and returns the response with its metadata.
+    fn chat_completion_meta(
+        req: ChatCompletionRequest,
+    ) -> ApiResponse<ChatCompletionResponse>;
+    /// Sends the given messages to the client's default model and returns the response.
+    fn chat(messages: Vec<ChatCompletionMessage>) -> ChatCompletionResponse;
+    /// Sends an audio transcription request and returns the response.
+    fn audio_transcription(
+        req: AudioTranscriptionRequest,
+    ) -> AudioTranscriptionResponse;
+    /// Sends an audio translation request and returns the response.
+    fn audio_translation(req: AudioTranslationRequest) -> AudioTranslationResponse;
+    /// Sends an audio speech request, saves the response to a file, and returns the response.
+    fn audio_speech(req: AudioSpeechRequest) -> AudioSpeechResponse;
+    /// Creates a fine-tuning job and returns the response.
+    fn create_fine_tuning_job(req: CreateFineTuningJobRequest) -> FineTuningJobObject;
+    /// Creates a fine-tuning job with per-request overrides, such as an idempotency key,
+    /// and returns the response.
+    fn create_fine_tuning_job_with_opts(
+        req: CreateFineTuningJobRequest,
+        opts: &RequestOptions,
+    ) -> FineTuningJobObject;
+    /// Lists fine-tuning jobs and returns the response.
+    fn list_fine_tuning_jobs() -> FineTuningPagination<FineTuningJobObject>;
+    /// Lists fine-tuning job events and returns the response.
+    fn list_fine_tuning_job_events(
+        req: ListFineTuningJobEventsRequest,
+    ) -> FineTuningPagination<FineTuningJobEvent>;
+    /// Lists the checkpoints of a fine-tuning job and returns the response.
+    fn list_fine_tuning_checkpoints(
+        job_id: String,
+        limit: Option<i64>,
+        after: Option<String>,
+    ) -> FineTuningPagination<FineTuningCheckpoint>;
+    /// Retrieves a fine-tuning job and returns the response.
+    fn retrieve_fine_tuning_job(
+        req: RetrieveFineTuningJobRequest,
+    ) -> FineTuningJobObject;
+    /// Cancels a fine-tuning job and returns the response.
+    fn cancel_fine_tuning_job(req: CancelFineTuningJobRequest) -> FineTuningJobObject;
+    /// Deletes a fine-tuned model owned by the organization and returns the response.
+    fn delete_model(model: String) -> DeletionStatus;
+    /// Creates a batch and returns the response.
+    fn create_batch(req: CreateBatchRequest) -> BatchObject;
+    /// Creates a batch with per-request overrides, such as an idempotency key, and returns
+    /// the response.
+    fn create_batch_with_opts(req: CreateBatchRequest, opts: &RequestOptions) -> BatchObject;
+    /// Retrieves a batch and returns the response.
+    fn retrieve_batch(batch_id: String) -> BatchObject;
+    /// Cancels a batch and returns the response.
+    fn cancel_batch(batch_id: String) -> BatchObject;
+    /// Lists batches and returns the response.
+    fn list_batches(limit: Option<i64>, after: Option<String>) -> ListBatch;
+    /// Creates a moderation request and returns the response.
+    fn create_moderation(req: CreateModerationRequest) -> CreateModerationResponse;
+    /// Creates an assistant and returns the response.
+    fn create_assistant(req: AssistantRequest) -> AssistantObject;
+    /// Retrieves an assistant and returns the response.
+    fn retrieve_assistant(assistant_id: String) -> AssistantObject;
+    /// Modifies an assistant and returns the response.
+    fn modify_assistant(
+        assistant_id: String,
+        req: AssistantRequest,
+    ) -> AssistantObject;
+    /// Deletes an assistant and returns the response.
+    fn delete_assistant(assistant_id: String) -> DeletionStatus;
+    /// Lists assistants and returns the response.
+    fn list_assistant(
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ListAssistant;
+    /// Creates an assistant file and returns the response.
+    fn create_assistant_file(
+        assistant_id: String,
+        req: AssistantFileRequest,
+    ) -> AssistantFileObject;
+    /// Retrieves an assistant file and returns the response.
+    fn retrieve_assistant_file(
+        assistant_id: String,
+        file_id: String,
+    ) -> AssistantFileObject;
+    /// Deletes an assistant file and returns the response.
+    fn delete_assistant_file(assistant_id: String, file_id: String) -> DeletionStatus;
+    /// Lists assistant files and returns the response.
+    fn list_assistant_file(
+        assistant_id: String,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ListAssistantFile;
+    /// Creates a thread and returns the response.
+    fn create_thread(req: CreateThreadRequest) -> ThreadObject;
+    /// Retrieves a thread and returns the response.
+    fn retrieve_thread(thread_id: String) -> ThreadObject;
+    /// Modifies a thread and returns the response.
+    fn modify_thread(thread_id: String, req: ModifyThreadRequest) -> ThreadObject;
+    /// Deletes a thread and returns the response.
+    fn delete_thread(thread_id: String) -> DeletionStatus;
+    /// Creates a message in a thread and returns the response.
+    fn create_message(thread_id: String, req: CreateMessageRequest) -> MessageObject;
+    /// Retrieves a message in a thread and returns the response.
+    fn retrieve_message(thread_id: String, message_id: String) -> MessageObject;
+    /// Modifies a message in a thread and returns the response.
+    fn modify_message(
+        thread_id: String,
+        message_id: String,
+        req: ModifyMessageRequest,
+    ) -> MessageObject;
+    /// Lists messages in a thread and returns the response, optionally narrowed to the
+    /// messages created by a single run.
+    fn list_messages(
+        thread_id: String,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+        run_id: Option<String>,
+    ) -> ListMessage;
+    /// Deletes a message from a thread and returns the deletion status.
+    fn delete_message(thread_id: String, message_id: String) -> DeletionStatus;
+    /// Retrieves a file associated with a message and returns the response.
+    fn retrieve_message_file(
+        thread_id: String,
+        message_id: String,
+        file_id: String,
+    ) -> MessageFileObject;
+    /// Lists files associated with a message and returns the response.
+    fn list_message_file(
+        thread_id: String,
+        message_id: String,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ListMessageFile;
+    /// Creates a run in a thread and returns the response.
+    fn create_run(thread_id: String, req: CreateRunRequest) -> RunObject;
+    /// Creates a run in a thread with per-request overrides, such as an idempotency key, and
+    /// returns the response.
+    fn create_run_with_opts(
+        thread_id: String,
+        req: CreateRunRequest,
+        opts: &RequestOptions,
+    ) -> RunObject;
+    /// Retrieves a run in a thread and returns the response.
+    fn retrieve_run(thread_id: String, run_id: String) -> RunObject;
+    /// Polls a run until it completes, fails or requires action, and returns it.
+    fn wait_for_run(
+        thread_id: String,
+        run_id: String,
+        min_interval: Duration,
+        max_interval: Duration,
+    ) -> RunObject;
+    /// Modifies a run in a thread and returns the response.
+    fn modify_run(
+        thread_id: String,
+        run_id: String,
+        req: ModifyRunRequest,
+    ) -> RunObject;
+    /// Lists runs in a thread and returns the response.
+    fn list_run(
+        thread_id: String,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ListRun;
+    /// Cancels a run in a thread and returns the response.
+    fn cancel_run(thread_id: String, run_id: String) -> RunObject;
+    /// Creates a thread and a run and returns the response.
+    fn create_thread_and_run(req: CreateThreadAndRunRequest) -> RunObject;
+    /// Creates a thread and a run with per-request overrides, such as an idempotency key,
+    /// and returns the response.
+    fn create_thread_and_run_with_opts(
+        req: CreateThreadAndRunRequest,
+        opts: &RequestOptions,
+    ) -> RunObject;
+    /// Retrieves a step in a run and returns the response.
+    fn retrieve_run_step(
+        thread_id: String,
+        run_id: String,
+        step_id: String,
+    ) -> RunStepObject;
+    /// Lists steps in a run and returns the response.
+    fn list_run_step(
+        thread_id: String,
+        run_id: String,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ListRunStep;
+    /// Creates a vector store and returns the response.
+    fn create_vector_store(req: CreateVectorStoreRequest) -> VectorStoreObject;
+    /// Retrieves a vector store and returns the response.
+    fn retrieve_vector_store(vector_store_id: String) -> VectorStoreObject;
+    /// Deletes a vector store and returns the response.
+    fn delete_vector_store(vector_store_id: String) -> DeletionStatus;
+    /// Lists vector stores and returns the response.
+    fn list_vector_stores(
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ListVectorStore;
+    /// Adds a file to a vector store and returns the response.
+    fn create_vector_store_file(
+        vector_store_id: String,
+        req: CreateVectorStoreFileRequest,
+    ) -> VectorStoreFileObject;
+    /// Creates a model response and returns it.
+    fn create_response(req: CreateResponseRequest) -> ResponseObject;
+    /// Retrieves a stored model response and returns it.
+    fn retrieve_response(response_id: String) -> ResponseObject;
+    /// Deletes a stored model response and returns the response.
+    fn delete_response(response_id: String) -> DeletionStatus;
+}
@@ -1,18 +1,32 @@
 //! This module defines the structures and methods for handling chat completion requests and responses.
 //! It includes the `ChatCompletionRequest`, `ChatCompletionResponse`, `ChatCompletionMessage`,
 //! `ChatCompletionChoice`, `Function`, `FunctionParameters`, `JSONSchemaType`, `JSONSchemaDefine`,
-//! `FinishReason`, `FinishDetails`, `ToolCall`, `ToolCallFunction`, and `Tool` structs along with their associated methods.
+//! `FinishReason`, `FinishDetails`, `InputAudio`, `AudioOutput`, `ChatAudio`, `StreamOptions`,
+//! `ChatCompletionChunk`, `ToolCallDelta`, `ToolCallAccumulator`, `ChatStream`, `ChatLogprobs`,
+//! `PredictionContent`, `ReasoningEffort`, `ToolCall`, `ToolCallFunction`, and `Tool` structs
+//! along with their associated methods.
 //! These structures facilitate the creation, serialization, and deserialization of chat completion requests and responses
 //! in various formats, allowing for customizable and extensible interactions with chat models.
 
+pub use crate::common::FinishReason;
 use crate::{
-    common::{MessageRole, Usage},
+    common::{validate_metadata, MessageRole, Usage},
+    error::APIError,
     impl_builder_methods,
     models::Model,
 };
-use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
-use serde_json::Value;
-use std::collections::HashMap;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures_util::{Stream, StreamExt};
+use serde::{
+    de::DeserializeOwned, ser::SerializeMap, Deserialize, Serialize, Serializer,
+};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// Represents the type of tool choice in the request.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -21,6 +35,8 @@ pub enum ToolChoiceType {
     None,
     /// Automatic tool choice.
     Auto,
+    /// The model must call at least one tool.
+    Required,
     /// Specific tool choice.
     ToolChoice {
         /// The chosen tool.
@@ -28,6 +44,63 @@ pub enum ToolChoiceType {
     },
 }
 
+/// Represents how much effort a reasoning model spends thinking before it answers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    /// Fastest and cheapest responses with the least reasoning.
+    Low,
+    /// Balance between speed and reasoning depth.
+    Medium,
+    /// Most thorough reasoning, at the cost of latency and tokens.
+    High,
+}
+
+/// Represents predicted output supplied to speed up responses that mostly repeat known text.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PredictionContent {
+    /// Type of the prediction, always `content`.
+    pub r#type: String,
+    /// Text the response is expected to largely match.
+    pub content: String,
+}
+
+impl PredictionContent {
+    /// Creates a new `PredictionContent` with the expected output text.
+    pub fn new(content: String) -> Self {
+        Self {
+            r#type: "content".to_owned(),
+            content,
+        }
+    }
+}
+
+/// Represents the spoken output requested from audio-capable models.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AudioOutput {
+    /// Voice the model answers with, such as `alloy`.
+    pub voice: String,
+    /// Audio format of the answer, such as `wav` or `mp3`.
+    pub format: String,
+}
+
+impl AudioOutput {
+    /// Creates a new `AudioOutput` with the given voice and format.
+    pub fn new(voice: impl Into<String>, format: String) -> Self {
+        Self {
+            voice: voice.into(),
+            format,
+        }
+    }
+}
+
+/// Represents the options of a streamed chat completion.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Whether to send a final chunk carrying the token usage of the whole request.
+    pub include_usage: bool,
+}
+
 /// Represents a request for chat completion.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChatCompletionRequest {
@@ -56,6 +129,9 @@ pub struct ChatCompletionRequest {
     /// Maximum number of tokens to generate.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<i64>,
+    /// Upper bound on generated tokens, including reasoning tokens, required by o-series models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<i64>,
     /// Positive values penalize new tokens based on their existing frequency in the text so far.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence_penalty: Option<f64>,
@@ -63,6 +139,9 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub frequency_penalty: Option<f64>,
     /// Modify the likelihood of specified tokens appearing in the completion.
+    ///
+    /// Keys are token IDs of the model's tokenizer and values range from -100 to 100; prefer
+    /// `with_logit_bias`, which enforces both.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logit_bias: Option<HashMap<String, i32>>,
     /// A unique identifier representing your end-user.
@@ -78,6 +157,37 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(serialize_with = "serialize_tool_choice")]
     pub tool_choice: Option<ToolChoiceType>,
+    /// Reasoning effort for o-series models; leave unset for other models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Whether to store the completion for later retrieval in the dashboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    /// Key-value tags attached to a stored completion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Predicted output, used to lower latency when much of the response is known in advance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prediction: Option<PredictionContent>,
+    /// Output types the model should generate, such as `["text", "audio"]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+    /// Spoken output settings, required when `audio` is requested in `modalities`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioOutput>,
+    /// Options for streamed responses, only sent along with `stream`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    /// Whether to return the log probabilities of the output tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    /// Number of most likely alternatives to return for each output token, requires `logprobs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<i64>,
+    /// Optional extra fields sent at the top level of the body, for parameters this crate does
+    /// not model yet.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<Map<String, Value>>,
 }
 
 impl ChatCompletionRequest {
@@ -94,6 +204,7 @@ impl ChatCompletionRequest {
             response_format: None,
             stop: None,
             max_tokens: None,
+            max_completion_tokens: None,
             presence_penalty: None,
             frequency_penalty: None,
             logit_bias: None,
@@ -101,6 +212,16 @@ impl ChatCompletionRequest {
             seed: None,
             tools: None,
             tool_choice: None,
+            reasoning_effort: None,
+            store: None,
+            metadata: None,
+            prediction: None,
+            modalities: None,
+            audio: None,
+            stream_options: None,
+            logprobs: None,
+            top_logprobs: None,
+            extra: None,
         }
     }
 
@@ -117,6 +238,7 @@ impl ChatCompletionRequest {
             response_format: None,
             stop: None,
             max_tokens: None,
+            max_completion_tokens: None,
             presence_penalty: None,
             frequency_penalty: None,
             logit_bias: None,
@@ -124,8 +246,197 @@ impl ChatCompletionRequest {
             seed: None,
             tools: None,
             tool_choice: None,
+            reasoning_effort: None,
+            store: None,
+            metadata: None,
+            prediction: None,
+            modalities: None,
+            audio: None,
+            stream_options: None,
+            logprobs: None,
+            top_logprobs: None,
+            extra: None,
+        }
+    }
+
+    /// Creates a new `ChatCompletionRequest` with a system message followed by a user message.
+    pub fn with_system(model: Model, system: &str, user: &str) -> Self {
+        Self::new_multi(
+            model,
+            vec![
+                ChatCompletionMessage {
+                    role: MessageRole::System,
+                    content: Content::Text(system.to_string()),
+                    name: None,
+                    tool_call_id: None,
+                    tool_calls: None,
+                },
+                ChatCompletionMessage {
+                    role: MessageRole::User,
+                    content: Content::Text(user.to_string()),
+                    name: None,
+                    tool_call_id: None,
+                    tool_calls: None,
+                },
+            ],
+        )
+    }
+
+    /// Adds a field sent at the top level of the body, for parameters this crate does not model
+    /// yet. The key should not repeat a modeled field, which would then be sent twice.
+    pub fn extra_field(mut self, key: &str, value: Value) -> Self {
+        self.extra
+            .get_or_insert_with(Map::new)
+            .insert(key.to_owned(), value);
+        self
+    }
+
+    /// Sets the logit bias from token IDs, returning an error if a bias falls outside -100 to
+    /// 100.
+    pub fn with_logit_bias(
+        mut self,
+        logit_bias: HashMap<u32, i32>,
+    ) -> Result<Self, APIError> {
+        for (token, bias) in &logit_bias {
+            check_logit_bias(&token.to_string(), *bias)?;
+        }
+        self.logit_bias = Some(
+            logit_bias
+                .into_iter()
+                .map(|(token, bias)| (token.to_string(), bias))
+                .collect(),
+        );
+        Ok(self)
+    }
+
+    /// Appends a message to the conversation.
+    pub fn push_message(&mut self, message: ChatCompletionMessage) {
+        self.messages.push(message);
+    }
+
+    /// Appends a user message with the given text to the conversation.
+    pub fn push_user(&mut self, text: &str) {
+        self.push_text(MessageRole::User, text);
+    }
+
+    /// Appends an assistant message with the given text to the conversation.
+    pub fn push_assistant(&mut self, text: &str) {
+        self.push_text(MessageRole::Assistant, text);
+    }
+
+    /// Appends the message of the first choice of `response`, so the next request continues the
+    /// conversation. Does nothing if the response has no choices.
+    pub fn extend_from_response(&mut self, response: &ChatCompletionResponse) {
+        if let Some(choice) = response.choices.first() {
+            self.push_message(choice.into());
+        }
+    }
+
+    /// Appends the first choice of `response` followed by one `tool` message per
+    /// `(tool_call_id, content)` pair in `results`, so the next request lets the model continue
+    /// from the output of the tools it called.
+    pub fn continue_with_tool_results<I>(
+        &mut self,
+        response: &ChatCompletionResponse,
+        results: I,
+    ) where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        self.extend_from_response(response);
+        for (tool_call_id, content) in results {
+            self.push_message(ChatCompletionMessage::tool_result(tool_call_id, content));
         }
     }
+
+    /// Appends a text message with the given role.
+    fn push_text(&mut self, role: MessageRole, text: &str) {
+        self.push_message(ChatCompletionMessage {
+            role,
+            content: Content::Text(text.to_string()),
+            name: None,
+            tool_call_id: None,
+            tool_calls: None,
+        });
+    }
+
+    /// Checks sampling parameters and metadata against their documented limits, reporting the first
+    /// violation.
+    pub fn validate(&self) -> Result<(), APIError> {
+        check_range("temperature", self.temperature, 0.0, 2.0)?;
+        check_range("top_p", self.top_p, 0.0, 1.0)?;
+        check_range("presence_penalty", self.presence_penalty, -2.0, 2.0)?;
+        check_range("frequency_penalty", self.frequency_penalty, -2.0, 2.0)?;
+        for (token, bias) in self.logit_bias.iter().flatten() {
+            if token.parse::<u32>().is_err() {
+                return Err(APIError::Unknown(format!(
+                    "logit_bias keys must be token IDs, got {:?}",
+                    token
+                )));
+            }
+            check_logit_bias(token, *bias)?;
+        }
+        if let Some(n) = self.n {
+            if n < 1 {
+                return Err(APIError::Unknown(format!(
+                    "n must be at least 1, got {}",
+                    n
+                )));
+            }
+        }
+        if let Some(metadata) = &self.metadata {
+            validate_metadata(metadata)?;
+        }
+        Ok(())
+    }
+
+    /// Drops the oldest messages other than system and developer ones until the estimated prompt
+    /// tokens fit within `max_prompt_tokens`, returning how many messages were removed.
+    #[cfg(feature = "tokenizer")]
+    pub fn truncate_to_budget(&mut self, max_prompt_tokens: usize) -> usize {
+        let mut removed = 0;
+        while crate::tokenizer::count_tokens_for(&self.model, &self.messages)
+            > max_prompt_tokens
+        {
+            match self.messages.iter().position(|message| {
+                !matches!(message.role, MessageRole::System | MessageRole::Developer)
+            }) {
+                Some(index) => {
+                    self.messages.remove(index);
+                    removed += 1;
+                }
+                None => break,
+            }
+        }
+        removed
+    }
+}
+
+/// Returns an error if the bias of `token` falls outside -100 to 100.
+fn check_logit_bias(token: &str, bias: i32) -> Result<(), APIError> {
+    if (-100..=100).contains(&bias) {
+        Ok(())
+    } else {
+        Err(APIError::Unknown(format!(
+            "logit_bias for token {} must be between -100 and 100, got {}",
+            token, bias
+        )))
+    }
+}
+
+/// Returns an error if `value` is set and falls outside `min..=max`.
+fn check_range(
+    name: &str,
+    value: Option<f64>,
+    min: f64,
+    max: f64,
+) -> Result<(), APIError> {
+    match value {
+        Some(value) if !(min..=max).contains(&value) => Err(APIError::Unknown(format!(
+            "{} must be between {} and {}, got {}",
+            name, min, max, value
+        ))),
+        _ => Ok(()),
+    }
 }
 
 impl From<&str> for ChatCompletionRequest {
@@ -137,6 +448,8 @@ impl From<&str> for ChatCompletionRequest {
                 role: MessageRole::User,
                 content: Content::Text(text.to_string()),
                 name: None,
+                tool_call_id: None,
+                tool_calls: None,
             },
         )
     }
@@ -151,6 +464,8 @@ impl From<String> for ChatCompletionRequest {
                 role: MessageRole::User,
                 content: Content::Text(text),
                 name: None,
+                tool_call_id: None,
+                tool_calls: None,
             },
         )
     }
@@ -165,17 +480,28 @@ impl_builder_methods!(
     stream: bool,
     stop: Vec<String>,
     max_tokens: i64,
+    max_completion_tokens: i64,
     presence_penalty: f64,
     frequency_penalty: f64,
     logit_bias: HashMap<String, i32>,
     user: String,
     seed: i64,
     tools: Vec<Tool>,
-    tool_choice: ToolChoiceType
+    tool_choice: ToolChoiceType,
+    reasoning_effort: ReasoningEffort,
+    store: bool,
+    metadata: HashMap<String, String>,
+    prediction: PredictionContent,
+    modalities: Vec<String>,
+    audio: AudioOutput,
+    stream_options: StreamOptions,
+    logprobs: bool,
+    top_logprobs: i64,
+    extra: Map<String, Value>
 );
 
 /// Represents the content of a message.
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Content {
     /// Text content.
     Text(String),
@@ -202,12 +528,40 @@ impl From<Vec<&str>> for Content {
                     image_url: Some(ImageUrlType {
                         url: text.to_string(),
                     }),
+                    input_audio: None,
                 })
                 .collect(),
         )
     }
 }
 
+impl Content {
+    /// Reads an audio file from disk into an `input_audio` content block, taking the format
+    /// from the file extension (`wav` or `mp3`).
+    pub async fn audio_from_path(path: &str) -> Result<Self, APIError> {
+        let format = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("wav") => "wav",
+            Some(ext) if ext.eq_ignore_ascii_case("mp3") => "mp3",
+            _ => {
+                return Err(APIError::Unknown(format!(
+                    "unsupported audio format for {}, expected wav or mp3",
+                    path
+                )))
+            }
+        };
+        let bytes = async_std::fs::read(path).await?;
+        Ok(Content::ImageUrl(vec![ImageUrl {
+            r#type: ContentType::input_audio,
+            text: None,
+            image_url: None,
+            input_audio: Some(InputAudio {
+                data: STANDARD.encode(bytes),
+                format: format.to_owned(),
+            }),
+        }]))
+    }
+}
+
 impl serde::Serialize for Content {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -220,6 +574,28 @@ impl serde::Serialize for Content {
     }
 }
 
+impl<'de> Deserialize<'de> for Content {
+    /// Deserializes content from its wire format, either a string or an array of content
+    /// blocks. A `null` content, as sent alongside tool calls, becomes empty text.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum WireContent {
+            Text(String),
+            Blocks(Vec<ImageUrl>),
+        }
+
+        Ok(match Option::<WireContent>::deserialize(deserializer)? {
+            Some(WireContent::Text(text)) => Content::Text(text),
+            Some(WireContent::Blocks(blocks)) => Content::ImageUrl(blocks),
+            None => Content::Text(String::new()),
+        })
+    }
+}
+
 /// Represents the type of content.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
@@ -228,6 +604,8 @@ pub enum ContentType {
     text,
     /// Image URL content type.
     image_url,
+    /// Base64-encoded audio content type.
+    input_audio,
 }
 
 /// Represents the URL of an image.
@@ -250,6 +628,18 @@ pub struct ImageUrl {
     /// Optional image URL type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image_url: Option<ImageUrlType>,
+    /// Optional base64-encoded audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio: Option<InputAudio>,
+}
+
+/// Represents base64-encoded audio sent to audio-capable models.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct InputAudio {
+    /// Base64-encoded audio data.
+    pub data: String,
+    /// Format of the audio data, `wav` or `mp3`.
+    pub format: String,
 }
 
 /// Represents a chat completion message.
@@ -262,6 +652,25 @@ pub struct ChatCompletionMessage {
     /// Optional name of the message sender.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Optional identifier of the tool call this message responds to, required for `tool` messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// Optional tool calls made by the assistant in this message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+impl ChatCompletionMessage {
+    /// Creates a `tool` message carrying the result of the given tool call.
+    pub fn tool_result(tool_call_id: String, content: String) -> Self {
+        Self {
+            role: MessageRole::Tool,
+            content: Content::Text(content),
+            name: None,
+            tool_call_id: Some(tool_call_id),
+            tool_calls: None,
+        }
+    }
 }
 
 impl From<&str> for ChatCompletionMessage {
@@ -271,6 +680,8 @@ impl From<&str> for ChatCompletionMessage {
             role: MessageRole::User,
             content: Content::Text(text.to_string()),
             name: None,
+            tool_call_id: None,
+            tool_calls: None,
         }
     }
 }
@@ -283,12 +694,72 @@ pub struct ChatCompletionMessageForResponse {
     /// Optional content of the message.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    /// Optional refusal message, set instead of `content` when the model declines to answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
     /// Optional name of the message sender.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// Optional tool calls related to the message.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
+    /// Optional spoken answer, returned when `audio` is requested in `modalities`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatAudio>,
+}
+
+/// Represents a spoken answer returned by an audio-capable model.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChatAudio {
+    /// Identifier of the audio, used to refer back to it in later turns.
+    pub id: String,
+    /// Base64-encoded audio in the requested format.
+    pub data: String,
+    /// Transcript of the audio.
+    pub transcript: String,
+    /// Timestamp after which the audio can no longer be referred to.
+    pub expires_at: i64,
+}
+
+impl ChatAudio {
+    /// Decodes the base64-encoded audio into raw bytes.
+    pub fn decode(&self) -> Result<Vec<u8>, APIError> {
+        STANDARD
+            .decode(&self.data)
+            .map_err(|err| APIError::Unknown(format!("invalid audio data: {}", err)))
+    }
+}
+
+impl ChatCompletionMessageForResponse {
+    /// Converts the response message into a request message, so it can be sent back as part of
+    /// the conversation history.
+    ///
+    /// A `null` content, as returned alongside tool calls, becomes empty text. Request messages
+    /// have no `audio` field, so a spoken answer is dropped; its `transcript` can be sent back as
+    /// text instead.
+    pub fn to_request_message(&self) -> ChatCompletionMessage {
+        ChatCompletionMessage {
+            role: self.role.clone(),
+            content: Content::Text(self.content.clone().unwrap_or_default()),
+            name: self.name.clone(),
+            tool_call_id: None,
+            tool_calls: self.tool_calls.clone(),
+        }
+    }
+}
+
+impl From<&ChatCompletionMessageForResponse> for ChatCompletionMessage {
+    /// Converts a response message into a request message, keeping its tool calls.
+    fn from(message: &ChatCompletionMessageForResponse) -> Self {
+        message.to_request_message()
+    }
+}
+
+impl From<&ChatCompletionChoice> for ChatCompletionMessage {
+    /// Converts the message of a choice into a request message, keeping its tool calls.
+    fn from(choice: &ChatCompletionChoice) -> Self {
+        choice.message.to_request_message()
+    }
 }
 
 /// Represents a choice in a chat completion response.
@@ -302,6 +773,69 @@ pub struct ChatCompletionChoice {
     pub finish_reason: Option<FinishReason>,
     /// Additional details for the finish reason.
     pub finish_details: Option<FinishDetails>,
+    /// Optional log probabilities of the output tokens, returned when `logprobs` is requested.
+    #[serde(default)]
+    pub logprobs: Option<ChatLogprobs>,
+}
+
+/// Represents the log probabilities of the tokens of a chat completion choice.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ChatLogprobs {
+    /// Optional log probabilities of the content tokens.
+    #[serde(default)]
+    pub content: Option<Vec<ChatTokenLogprob>>,
+    /// Optional log probabilities of the refusal tokens.
+    #[serde(default)]
+    pub refusal: Option<Vec<ChatTokenLogprob>>,
+}
+
+/// Represents the log probability of an output token and its most likely alternatives.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChatTokenLogprob {
+    /// The token.
+    pub token: String,
+    /// Log probability of the token.
+    pub logprob: f64,
+    /// Optional UTF-8 bytes of the token, useful when a character spans several tokens.
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+    /// Most likely alternatives at this position, as many as `top_logprobs` requested.
+    #[serde(default)]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+/// Represents one of the most likely alternatives for an output token.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TopLogprob {
+    /// The alternative token.
+    pub token: String,
+    /// Log probability of the alternative token.
+    pub logprob: f64,
+    /// Optional UTF-8 bytes of the alternative token.
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+}
+
+impl ChatCompletionChoice {
+    /// Returns whether the model stopped to call one or more tools.
+    pub fn is_tool_call(&self) -> bool {
+        self.finish_reason == Some(FinishReason::tool_calls)
+    }
+
+    /// Returns whether the model reached a natural stopping point or a stop sequence.
+    pub fn is_complete(&self) -> bool {
+        self.finish_reason == Some(FinishReason::stop)
+    }
+
+    /// Returns whether the output was cut off by the token limit.
+    pub fn was_truncated(&self) -> bool {
+        self.finish_reason == Some(FinishReason::length)
+    }
+
+    /// Returns the refusal message if the model declined to answer.
+    pub fn refusal(&self) -> Option<&str> {
+        self.message.refusal.as_deref()
+    }
 }
 
 /// Represents a chat completion response.
@@ -330,7 +864,300 @@ impl ChatCompletionResponse {
     pub fn get_choice(&self) -> String {
         self.choices[0].message.content.clone().unwrap_or_default()
     }
+
+    /// Returns whether both responses report the same `system_fingerprint`, meaning they were
+    /// produced by the same backend configuration.
+    ///
+    /// Responses without a fingerprint are never considered to share a backend, since seeded
+    /// requests are only reproducible when the fingerprints match.
+    pub fn same_backend_as(&self, other: &ChatCompletionResponse) -> bool {
+        match (&self.system_fingerprint, &other.system_fingerprint) {
+            (Some(fingerprint), Some(other)) => fingerprint == other,
+            _ => false,
+        }
+    }
+}
+
+/// Represents a chunk of a streamed chat completion.
+///
+/// With `include_usage` set in `stream_options`, the last chunk has no choices and carries the
+/// token usage of the whole request.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChatCompletionChunk {
+    /// Unique identifier for the completion, shared by all of its chunks.
+    pub id: String,
+    /// Object type, typically "chat.completion.chunk".
+    pub object: String,
+    /// Creation timestamp.
+    pub created: i64,
+    /// Model used for the completion.
+    pub model: String,
+    /// List of incremental choices in the chunk.
+    pub choices: Vec<ChatCompletionChunkChoice>,
+    /// Optional usage information, only set on the final chunk.
+    #[serde(default)]
+    pub usage: Option<Usage>,
+    /// Optional system fingerprint.
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
+}
+
+/// Represents an incremental choice in a streamed chat completion.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChatCompletionChunkChoice {
+    /// Index of the choice.
+    pub index: i64,
+    /// Changes to the message of the choice.
+    pub delta: ChatCompletionDelta,
+    /// Reason for finishing the response, set on the last chunk of the choice.
+    pub finish_reason: Option<FinishReason>,
+    /// Optional log probabilities of the tokens in the chunk.
+    #[serde(default)]
+    pub logprobs: Option<ChatLogprobs>,
+}
+
+/// Represents the changes to a message carried by a streamed chunk.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ChatCompletionDelta {
+    /// Optional role of the message sender, set on the first chunk.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<MessageRole>,
+    /// Optional fragment of the message content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Optional fragment of the refusal message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+    /// Optional fragments of tool calls.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// Represents a fragment of a tool call, keyed by its index in the message.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ToolCallDelta {
+    /// Index of the tool call the fragment belongs to.
+    pub index: usize,
+    /// Optional identifier of the tool call, set on its first fragment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Optional type of the tool call, set on its first fragment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    /// Optional fragment of the function name and arguments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<ToolCallFunction>,
+}
+
+/// Represents the stream of chunks returned by a streamed chat completion.
+pub struct ChatStream {
+    /// Underlying stream of deserialized chunks.
+    inner: Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk, APIError>> + Send>>,
+}
+
+impl ChatStream {
+    /// Wraps a stream of chunks.
+    pub(crate) fn new(
+        inner: impl Stream<Item = Result<ChatCompletionChunk, APIError>> + Send + 'static,
+    ) -> Self {
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+
+    /// Consumes the stream and assembles the chunks into a complete response, as if the request
+    /// had not been streamed.
+    ///
+    /// Content and tool-call fragments are concatenated per choice. Usage is only reported if
+    /// `include_usage` was set in `stream_options`, and is zero otherwise.
+    pub async fn collect_response(mut self) -> Result<ChatCompletionResponse, APIError> {
+        let mut response: Option<ChatCompletionResponse> = None;
+        let mut choices: BTreeMap<i64, ChoiceBuilder> = BTreeMap::new();
+        while let Some(chunk) = self.next().await {
+            let chunk = chunk?;
+            let response = response.get_or_insert_with(|| ChatCompletionResponse {
+                id: chunk.id.clone(),
+                object: "chat.completion".to_owned(),
+                created: chunk.created,
+                model: chunk.model.clone(),
+                choices: Vec::new(),
+                usage: Usage::default(),
+                system_fingerprint: None,
+                headers: None,
+            });
+            if let Some(usage) = chunk.usage {
+                response.usage = usage;
+            }
+            if chunk.system_fingerprint.is_some() {
+                response.system_fingerprint = chunk.system_fingerprint;
+            }
+            for choice in chunk.choices {
+                choices.entry(choice.index).or_default().push(choice);
+            }
+        }
+        let mut response = response.ok_or_else(|| {
+            APIError::Unknown("stream ended without any chunk".to_owned())
+        })?;
+        response.choices = choices
+            .into_iter()
+            .map(|(index, choice)| choice.build(index))
+            .collect();
+        Ok(response)
+    }
 }
+
+impl Stream for ChatStream {
+    type Item = Result<ChatCompletionChunk, APIError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// Accumulates the streamed fragments of a single choice.
+#[derive(Default)]
+struct ChoiceBuilder {
+    /// Role of the message sender, taken from the first chunk.
+    role: Option<MessageRole>,
+    /// Content received so far.
+    content: Option<String>,
+    /// Refusal received so far.
+    refusal: Option<String>,
+    /// Tool calls received so far.
+    tool_calls: ToolCallAccumulator,
+    /// Reason for finishing the response, taken from the last chunk.
+    finish_reason: Option<FinishReason>,
+    /// Log probabilities received so far.
+    logprobs: Option<ChatLogprobs>,
+}
+
+impl ChoiceBuilder {
+    /// Merges the fragments of a chunk into the choice.
+    fn push(&mut self, choice: ChatCompletionChunkChoice) {
+        let delta = choice.delta;
+        if delta.role.is_some() {
+            self.role = delta.role;
+        }
+        if let Some(content) = delta.content {
+            self.content
+                .get_or_insert_with(String::new)
+                .push_str(&content);
+        }
+        if let Some(refusal) = delta.refusal {
+            self.refusal
+                .get_or_insert_with(String::new)
+                .push_str(&refusal);
+        }
+        for tool_call in delta.tool_calls.unwrap_or_default() {
+            self.tool_calls.push(tool_call);
+        }
+        if let Some(logprobs) = choice.logprobs {
+            let merged = self.logprobs.get_or_insert_with(ChatLogprobs::default);
+            for (merged, tokens) in [
+                (&mut merged.content, logprobs.content),
+                (&mut merged.refusal, logprobs.refusal),
+            ] {
+                if let Some(tokens) = tokens {
+                    merged.get_or_insert_with(Vec::new).extend(tokens);
+                }
+            }
+        }
+        if choice.finish_reason.is_some() {
+            self.finish_reason = choice.finish_reason;
+        }
+    }
+
+    /// Turns the accumulated fragments into a complete choice.
+    fn build(self, index: i64) -> ChatCompletionChoice {
+        let tool_calls = self.tool_calls.finish();
+        ChatCompletionChoice {
+            index,
+            message: ChatCompletionMessageForResponse {
+                role: self.role.unwrap_or(MessageRole::Assistant),
+                content: self.content,
+                refusal: self.refusal,
+                name: None,
+                tool_calls: if tool_calls.is_empty() {
+                    None
+                } else {
+                    Some(tool_calls)
+                },
+                audio: None,
+            },
+            finish_reason: self.finish_reason,
+            finish_details: None,
+            logprobs: self.logprobs,
+        }
+    }
+}
+
+/// Reassembles tool calls from the fragments streamed across chunks.
+///
+/// The identifier, type and function name arrive on the first fragment of each call, while
+/// the arguments are split across many fragments sharing the same index.
+#[derive(Debug, Clone, Default)]
+pub struct ToolCallAccumulator {
+    /// Tool calls received so far, keyed by their index.
+    tool_calls: BTreeMap<usize, ToolCall>,
+}
+
+impl ToolCallAccumulator {
+    /// Creates a new, empty `ToolCallAccumulator`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges a fragment into the tool call at its index.
+    pub fn push(&mut self, delta: ToolCallDelta) {
+        let entry = self
+            .tool_calls
+            .entry(delta.index)
+            .or_insert_with(|| ToolCall {
+                id: String::new(),
+                r#type: "function".to_owned(),
+                function: ToolCallFunction {
+                    name: None,
+                    arguments: None,
+                },
+            });
+        if let Some(id) = delta.id {
+            entry.id = id;
+        }
+        if let Some(r#type) = delta.r#type {
+            entry.r#type = r#type;
+        }
+        if let Some(function) = delta.function {
+            if let Some(name) = function.name {
+                entry
+                    .function
+                    .name
+                    .get_or_insert_with(String::new)
+                    .push_str(&name);
+            }
+            if let Some(arguments) = function.arguments {
+                entry
+                    .function
+                    .arguments
+                    .get_or_insert_with(String::new)
+                    .push_str(&arguments);
+            }
+        }
+    }
+
+    /// Returns whether no fragment has been received yet.
+    pub fn is_empty(&self) -> bool {
+        self.tool_calls.is_empty()
+    }
+
+    /// Returns the complete tool calls, ordered by index.
+    pub fn finish(self) -> Vec<ToolCall> {
+        self.tool_calls.into_values().collect()
+    }
+}
+
 /// Represents a function definition.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct Function {
@@ -343,6 +1170,23 @@ pub struct Function {
     pub parameters: FunctionParameters,
 }
 
+impl Function {
+    /// Checks tool-call arguments returned by the model against the declared parameters,
+    /// reporting the first missing required field or mismatched type.
+    pub fn validate_arguments(&self, args: &str) -> Result<(), APIError> {
+        let args: Value = serde_json::from_str(args)?;
+        let schema = JSONSchemaDefine {
+            schema_type: Some(self.parameters.schema_type.clone()),
+            properties: self.parameters.properties.clone(),
+            required: self.parameters.required.clone(),
+            ..Default::default()
+        };
+        schema.check("arguments", &args).map_err(|err| {
+            APIError::Unknown(format!("invalid arguments for {}: {}", self.name, err))
+        })
+    }
+}
+
 /// Represents the JSON schema type.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -371,7 +1215,7 @@ pub struct JSONSchemaDefine {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Optional enumeration values.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
     pub enum_values: Option<Vec<String>>,
     /// Optional properties of the schema.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -384,6 +1228,97 @@ pub struct JSONSchemaDefine {
     pub items: Option<Box<JSONSchemaDefine>>,
 }
 
+impl JSONSchemaDefine {
+    /// Creates a string schema with the given description.
+    pub fn string(description: &str) -> Self {
+        Self {
+            schema_type: Some(JSONSchemaType::String),
+            description: Some(description.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a string schema restricted to the given values.
+    pub fn enum_of(values: &[&str]) -> Self {
+        Self {
+            schema_type: Some(JSONSchemaType::String),
+            enum_values: Some(values.iter().map(|value| value.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an object schema with the given properties, of which `required` must be present.
+    pub fn object(properties: Vec<(&str, JSONSchemaDefine)>, required: &[&str]) -> Self {
+        Self {
+            schema_type: Some(JSONSchemaType::Object),
+            properties: Some(
+                properties
+                    .into_iter()
+                    .map(|(name, schema)| (name.to_owned(), Box::new(schema)))
+                    .collect(),
+            ),
+            required: Some(required.iter().map(|name| name.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an array schema whose elements match `item`.
+    pub fn array_of(item: JSONSchemaDefine) -> Self {
+        Self {
+            schema_type: Some(JSONSchemaType::Array),
+            items: Some(Box::new(item)),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the description of the schema.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Checks `value` against the type, allowed values, required properties and nested
+    /// schemas, describing the first violation found at `path`.
+    fn check(&self, path: &str, value: &Value) -> Result<(), String> {
+        if let Some(schema_type) = &self.schema_type {
+            let matches = match schema_type {
+                JSONSchemaType::Object => value.is_object(),
+                JSONSchemaType::Number => value.is_number(),
+                JSONSchemaType::String => value.is_string(),
+                JSONSchemaType::Array => value.is_array(),
+                JSONSchemaType::Null => value.is_null(),
+                JSONSchemaType::Boolean => value.is_boolean(),
+            };
+            if !matches {
+                return Err(format!("{} must be of type {:?}", path, schema_type));
+            }
+        }
+        if let (Some(values), Some(text)) = (&self.enum_values, value.as_str()) {
+            if !values.iter().any(|allowed| allowed == text) {
+                return Err(format!("{} must be one of {:?}", path, values));
+            }
+        }
+        if let Some(object) = value.as_object() {
+            for name in self.required.iter().flatten() {
+                if !object.contains_key(name) {
+                    return Err(format!("{}.{} is required", path, name));
+                }
+            }
+            for (name, schema) in self.properties.iter().flatten() {
+                if let Some(property) = object.get(name) {
+                    schema.check(&format!("{}.{}", path, name), property)?;
+                }
+            }
+        }
+        if let (Some(items), Some(array)) = (&self.items, value.as_array()) {
+            for (index, item) in array.iter().enumerate() {
+                items.check(&format!("{}[{}]", path, index), item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Represents the parameters of a function using JSON schema.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct FunctionParameters {
@@ -398,22 +1333,6 @@ pub struct FunctionParameters {
     pub required: Option<Vec<String>>,
 }
 
-/// Reason for finishing the response.
-#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
-#[allow(non_camel_case_types)]
-pub enum FinishReason {
-    /// Finished due to reaching stop condition.
-    stop,
-    /// Finished due to reaching maximum length.
-    length,
-    /// Finished due to content filtering.
-    content_filter,
-    /// Finished due to tool calls.
-    tool_calls,
-    /// Null finish reason.
-    null,
-}
-
 /// Additional details for the finish reason.
 #[derive(Debug, Deserialize, Serialize)]
 #[allow(non_camel_case_types)]
@@ -446,7 +1365,18 @@ pub struct ToolCallFunction {
     pub arguments: Option<String>,
 }
 
+impl ToolCallFunction {
+    /// Deserializes the JSON arguments of the call, treating missing arguments as `{}`.
+    pub fn parse_arguments<T: DeserializeOwned>(&self) -> Result<T, APIError> {
+        let arguments = self.arguments.as_deref().unwrap_or("{}");
+        Ok(serde_json::from_str(arguments)?)
+    }
+}
+
 /// Serializes the tool choice type.
+///
+/// An unset `tool_choice` is skipped entirely, while `Some(ToolChoiceType::None)` is sent as
+/// `"none"` to explicitly forbid tool calls. A specific tool is named by its function name only.
 fn serialize_tool_choice<S>(
     value: &Option<ToolChoiceType>,
     serializer: S,
@@ -457,10 +1387,17 @@ where
     match value {
         Some(ToolChoiceType::None) => serializer.serialize_str("none"),
         Some(ToolChoiceType::Auto) => serializer.serialize_str("auto"),
+        Some(ToolChoiceType::Required) => serializer.serialize_str("required"),
         Some(ToolChoiceType::ToolChoice { tool }) => {
             let mut map = serializer.serialize_map(Some(2))?;
             map.serialize_entry("type", &tool.r#type)?;
-            map.serialize_entry("function", &tool.function)?;
+            map.serialize_entry(
+                "function",
+                &Map::from_iter([(
+                    "name".to_owned(),
+                    Value::String(tool.function.name.clone()),
+                )]),
+            )?;
             map.end()
         }
         None => serializer.serialize_none(),
@@ -483,3 +1420,756 @@ pub enum ToolType {
     /// Represents a function tool type.
     Function,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn with_system_builds_system_then_user_message() {
+        let req = ChatCompletionRequest::with_system(
+            Model::custom("gpt-4o"),
+            "You are terse.",
+            "Hi there",
+        );
+        assert_eq!(req.model, "gpt-4o");
+        assert_eq!(req.messages.len(), 2);
+        assert_eq!(req.messages[0].role, MessageRole::System);
+        assert!(
+            matches!(&req.messages[0].content, Content::Text(text) if text == "You are terse.")
+        );
+        assert_eq!(req.messages[1].role, MessageRole::User);
+        assert!(
+            matches!(&req.messages[1].content, Content::Text(text) if text == "Hi there")
+        );
+    }
+
+    fn request() -> ChatCompletionRequest {
+        ChatCompletionRequest::new(Model::custom("gpt-4o"), "Hello".into())
+    }
+
+    #[test]
+    fn validate_accepts_range_boundaries() {
+        for req in [
+            request(),
+            request().temperature(0.0).top_p(0.0),
+            request().temperature(2.0).top_p(1.0),
+            request().presence_penalty(-2.0).frequency_penalty(-2.0),
+            request().presence_penalty(2.0).frequency_penalty(2.0),
+            request().n(1),
+        ] {
+            assert!(req.validate().is_ok(), "{:?}", req);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_values_outside_ranges() {
+        for (req, message) in [
+            (
+                request().temperature(-0.1),
+                "temperature must be between 0 and 2",
+            ),
+            (
+                request().temperature(2.1),
+                "temperature must be between 0 and 2",
+            ),
+            (request().top_p(-0.1), "top_p must be between 0 and 1"),
+            (request().top_p(1.1), "top_p must be between 0 and 1"),
+            (
+                request().presence_penalty(-2.1),
+                "presence_penalty must be between -2 and 2",
+            ),
+            (
+                request().presence_penalty(2.1),
+                "presence_penalty must be between -2 and 2",
+            ),
+            (
+                request().frequency_penalty(-2.1),
+                "frequency_penalty must be between -2 and 2",
+            ),
+            (
+                request().frequency_penalty(2.1),
+                "frequency_penalty must be between -2 and 2",
+            ),
+            (request().n(0), "n must be at least 1"),
+        ] {
+            match req.validate() {
+                Err(APIError::Unknown(err)) => {
+                    assert!(err.starts_with(message), "{}", err)
+                }
+                other => panic!("expected {:?} to fail, got {:?}", message, other),
+            }
+        }
+    }
+
+    #[cfg(feature = "tokenizer")]
+    #[test]
+    fn truncate_to_budget_keeps_system_message_and_newest_turns() {
+        let system = ChatCompletionMessage {
+            role: MessageRole::System,
+            ..ChatCompletionMessage::from("You are a terse assistant.")
+        };
+        let mut req = ChatCompletionRequest::new(Model::custom("gpt-4"), system.clone());
+        for turn in ["first question", "second question", "third question"] {
+            req.messages.push(turn.into());
+        }
+        let kept = vec![system, ChatCompletionMessage::from("third question")];
+        let budget = crate::tokenizer::count_tokens_for("gpt-4", &kept);
+
+        assert_eq!(req.truncate_to_budget(budget), 2);
+        assert_eq!(
+            serde_json::to_value(&req.messages).unwrap(),
+            serde_json::to_value(&kept).unwrap()
+        );
+        assert_eq!(req.truncate_to_budget(budget), 0);
+        assert_eq!(req.truncate_to_budget(0), 1);
+        assert_eq!(req.messages.len(), 1);
+        assert_eq!(req.messages[0].role, MessageRole::System);
+    }
+
+    #[test]
+    fn serializes_every_tool_choice_form() {
+        let tool_choice = |choice: Option<ToolChoiceType>| {
+            let mut req = request();
+            req.tool_choice = choice;
+            serde_json::to_value(&req)
+                .unwrap()
+                .get("tool_choice")
+                .cloned()
+        };
+        let tool = Tool {
+            r#type: ToolType::Function,
+            function: Function {
+                name: "get_weather".to_owned(),
+                description: None,
+                parameters: FunctionParameters {
+                    schema_type: JSONSchemaType::Object,
+                    properties: None,
+                    required: None,
+                },
+            },
+        };
+
+        assert_eq!(tool_choice(None), None);
+        assert_eq!(tool_choice(Some(ToolChoiceType::None)), Some(json!("none")));
+        assert_eq!(tool_choice(Some(ToolChoiceType::Auto)), Some(json!("auto")));
+        assert_eq!(
+            tool_choice(Some(ToolChoiceType::Required)),
+            Some(json!("required"))
+        );
+        assert_eq!(
+            tool_choice(Some(ToolChoiceType::ToolChoice { tool })),
+            Some(json!({
+                "type": "function",
+                "function": { "name": "get_weather" }
+            }))
+        );
+    }
+
+    fn choice(finish_reason: Value) -> ChatCompletionChoice {
+        serde_json::from_value(json!({
+            "index": 0,
+            "message": { "role": "assistant", "content": "Hi" },
+            "finish_reason": finish_reason
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn finish_reason_helpers_match_their_reason() {
+        let tool_call = choice(json!("tool_calls"));
+        assert!(tool_call.is_tool_call() && !tool_call.is_complete());
+        let stop = choice(json!("stop"));
+        assert!(stop.is_complete() && !stop.was_truncated());
+        let length = choice(json!("length"));
+        assert!(length.was_truncated() && !length.is_tool_call());
+        let pending = choice(Value::Null);
+        assert!(
+            !pending.is_complete() && !pending.is_tool_call() && !pending.was_truncated()
+        );
+    }
+
+    #[test]
+    fn unknown_finish_reason_falls_back() {
+        let choice = choice(json!("some_future_reason"));
+        assert_eq!(choice.finish_reason, Some(FinishReason::unknown));
+        assert!(
+            !choice.is_complete() && !choice.is_tool_call() && !choice.was_truncated()
+        );
+    }
+
+    #[test]
+    fn tool_result_round_trips() {
+        let message = ChatCompletionMessage::tool_result(
+            "call_abc123".to_owned(),
+            "{\"ok\":true}".to_owned(),
+        );
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(
+            value,
+            json!({ "role": "tool", "content": "{\"ok\":true}", "tool_call_id": "call_abc123" })
+        );
+        let parsed: ChatCompletionMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.role, MessageRole::Tool);
+        assert_eq!(parsed.tool_call_id.as_deref(), Some("call_abc123"));
+        assert!(matches!(parsed.content, Content::Text(text) if text == "{\"ok\":true}"));
+    }
+
+    #[test]
+    fn schema_helpers_serialize_to_expected_json() {
+        let to_json = |schema: JSONSchemaDefine| serde_json::to_value(schema).unwrap();
+        assert_eq!(
+            to_json(JSONSchemaDefine::string("City name")),
+            json!({ "type": "string", "description": "City name" })
+        );
+        assert_eq!(
+            to_json(JSONSchemaDefine::enum_of(&["celsius", "fahrenheit"])),
+            json!({ "type": "string", "enum": ["celsius", "fahrenheit"] })
+        );
+        assert_eq!(
+            to_json(JSONSchemaDefine::array_of(JSONSchemaDefine::string("Tag"))),
+            json!({ "type": "array", "items": { "type": "string", "description": "Tag" } })
+        );
+        assert_eq!(
+            to_json(
+                JSONSchemaDefine::object(
+                    vec![
+                        ("city", JSONSchemaDefine::string("City name")),
+                        ("unit", JSONSchemaDefine::enum_of(&["celsius"])),
+                    ],
+                    &["city"],
+                )
+                .with_description("Weather query")
+            ),
+            json!({
+                "type": "object",
+                "description": "Weather query",
+                "properties": {
+                    "city": { "type": "string", "description": "City name" },
+                    "unit": { "type": "string", "enum": ["celsius"] }
+                },
+                "required": ["city"]
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_chat_logprobs() {
+        let choice: ChatCompletionChoice = serde_json::from_value(json!({
+            "index": 0,
+            "message": { "role": "assistant", "content": "Hello" },
+            "logprobs": {
+                "content": [{
+                    "token": "Hello",
+                    "logprob": -0.31725305,
+                    "bytes": [72, 101, 108, 108, 111],
+                    "top_logprobs": [
+                        { "token": "Hello", "logprob": -0.31725305, "bytes": [72, 101, 108, 108, 111] },
+                        { "token": "Hi", "logprob": -1.3190403, "bytes": [72, 105] }
+                    ]
+                }],
+                "refusal": null
+            },
+            "finish_reason": "stop"
+        }))
+        .unwrap();
+        let logprobs = choice.logprobs.unwrap();
+        assert!(logprobs.refusal.is_none());
+        let content = logprobs.content.unwrap();
+        assert_eq!(content[0].token, "Hello");
+        assert_eq!(content[0].logprob, -0.31725305);
+        assert_eq!(content[0].bytes.as_deref(), Some(&b"Hello"[..]));
+        assert_eq!(content[0].top_logprobs.len(), 2);
+        assert_eq!(content[0].top_logprobs[1].token, "Hi");
+    }
+
+    #[test]
+    fn deserializes_null_chat_logprobs() {
+        let choice: ChatCompletionChoice = serde_json::from_value(json!({
+            "index": 0,
+            "message": { "role": "assistant", "content": "Hello" },
+            "logprobs": null,
+            "finish_reason": "stop"
+        }))
+        .unwrap();
+        assert!(choice.logprobs.is_none());
+    }
+
+    #[test]
+    fn text_content_round_trips() {
+        let value = serde_json::to_value(Content::Text("Hello".to_owned())).unwrap();
+        assert_eq!(value, json!("Hello"));
+        let content: Content = serde_json::from_value(value).unwrap();
+        assert_eq!(content, Content::Text("Hello".to_owned()));
+    }
+
+    #[test]
+    fn block_content_round_trips() {
+        let blocks = vec![
+            ImageUrl {
+                r#type: ContentType::text,
+                text: Some("What is in this image?".to_owned()),
+                image_url: None,
+                input_audio: None,
+            },
+            ImageUrl {
+                r#type: ContentType::image_url,
+                text: None,
+                image_url: Some(ImageUrlType {
+                    url: "https://example.com/cat.png".to_owned(),
+                }),
+                input_audio: None,
+            },
+        ];
+        let value = serde_json::to_value(Content::ImageUrl(blocks.clone())).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                { "type": "text", "text": "What is in this image?" },
+                { "type": "image_url", "image_url": { "url": "https://example.com/cat.png" } }
+            ])
+        );
+        let content: Content = serde_json::from_value(value).unwrap();
+        assert_eq!(content, Content::ImageUrl(blocks));
+    }
+
+    #[test]
+    fn extra_fields_are_sent_at_top_level() {
+        let req = request()
+            .temperature(0.5)
+            .extra_field("service_tier", json!("flex"))
+            .extra_field(
+                "web_search_options",
+                json!({ "search_context_size": "low" }),
+            );
+        let body = serde_json::to_value(&req).unwrap();
+        assert_eq!(body["service_tier"], "flex");
+        assert_eq!(
+            body["web_search_options"],
+            json!({ "search_context_size": "low" })
+        );
+        assert_eq!(body["temperature"], 0.5);
+        assert!(body.get("extra").is_none());
+    }
+
+    #[test]
+    fn choice_converts_to_assistant_message_with_tool_calls() {
+        let choice: ChatCompletionChoice = serde_json::from_value(json!({
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": "call_abc123",
+                    "type": "function",
+                    "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+                }]
+            },
+            "finish_reason": "tool_calls"
+        }))
+        .unwrap();
+        let message = ChatCompletionMessage::from(&choice);
+        assert_eq!(message.role, MessageRole::Assistant);
+        assert_eq!(message.content, Content::Text(String::new()));
+        let tool_calls = message.tool_calls.unwrap();
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].id, "call_abc123");
+        assert_eq!(tool_calls[0].function.name.as_deref(), Some("get_weather"));
+        assert_eq!(
+            tool_calls[0].function.arguments.as_deref(),
+            Some("{\"city\":\"Paris\"}")
+        );
+    }
+
+    fn chunk(choices: Value, usage: Value) -> ChatCompletionChunk {
+        serde_json::from_value(json!({
+            "id": "chatcmpl-abc123",
+            "object": "chat.completion.chunk",
+            "created": 1694268190,
+            "model": "gpt-4o",
+            "system_fingerprint": "fp_44709d6fcb",
+            "choices": choices,
+            "usage": usage
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn collect_response_merges_chunks() {
+        let chunks = vec![
+            chunk(
+                json!([{ "index": 0, "delta": { "role": "assistant", "content": "" } }]),
+                Value::Null,
+            ),
+            chunk(
+                json!([{ "index": 0, "delta": { "content": "Hello" } }]),
+                Value::Null,
+            ),
+            chunk(
+                json!([{ "index": 0, "delta": { "content": " there" } }]),
+                Value::Null,
+            ),
+            chunk(
+                json!([{ "index": 0, "delta": {}, "finish_reason": "stop" }]),
+                Value::Null,
+            ),
+            chunk(
+                json!([]),
+                json!({ "prompt_tokens": 9, "completion_tokens": 2, "total_tokens": 11 }),
+            ),
+        ];
+        let stream =
+            ChatStream::new(futures_util::stream::iter(chunks.into_iter().map(Ok)));
+
+        let response = stream.collect_response().await.unwrap();
+
+        assert_eq!(response.id, "chatcmpl-abc123");
+        assert_eq!(response.object, "chat.completion");
+        assert_eq!(
+            response.system_fingerprint.as_deref(),
+            Some("fp_44709d6fcb")
+        );
+        assert_eq!(response.choices.len(), 1);
+        let choice = &response.choices[0];
+        assert_eq!(choice.message.role, MessageRole::Assistant);
+        assert_eq!(choice.message.content.as_deref(), Some("Hello there"));
+        assert_eq!(choice.finish_reason, Some(FinishReason::stop));
+        assert_eq!(response.usage.prompt_tokens, 9);
+        assert_eq!(response.usage.completion_tokens, 2);
+        assert_eq!(response.usage.total_tokens, 11);
+    }
+
+    #[test]
+    fn tool_call_accumulator_reassembles_split_arguments() {
+        let deltas = [
+            json!({ "index": 0, "id": "call_a", "type": "function",
+                    "function": { "name": "get_weather", "arguments": "" } }),
+            json!({ "index": 1, "id": "call_b", "type": "function",
+                    "function": { "name": "get_time", "arguments": "{\"tz\":" } }),
+            json!({ "index": 0, "function": { "arguments": "{\"city\"" } }),
+            json!({ "index": 0, "function": { "arguments": ":\"Paris\"}" } }),
+            json!({ "index": 1, "function": { "arguments": "\"UTC\"}" } }),
+        ];
+        let mut accumulator = ToolCallAccumulator::new();
+        assert!(accumulator.is_empty());
+        for delta in deltas {
+            accumulator.push(serde_json::from_value(delta).unwrap());
+        }
+
+        let tool_calls = accumulator.finish();
+        assert_eq!(tool_calls.len(), 2);
+        assert_eq!(tool_calls[0].id, "call_a");
+        assert_eq!(tool_calls[0].function.name.as_deref(), Some("get_weather"));
+        assert_eq!(
+            tool_calls[0].function.arguments.as_deref(),
+            Some("{\"city\":\"Paris\"}")
+        );
+        assert_eq!(tool_calls[1].id, "call_b");
+        assert_eq!(
+            tool_calls[1].function.arguments.as_deref(),
+            Some("{\"tz\":\"UTC\"}")
+        );
+        let args: Value =
+            serde_json::from_str(tool_calls[0].function.arguments.as_deref().unwrap())
+                .unwrap();
+        assert_eq!(args, json!({ "city": "Paris" }));
+    }
+
+    fn weather_function() -> Function {
+        let JSONSchemaDefine {
+            properties,
+            required,
+            ..
+        } = JSONSchemaDefine::object(
+            vec![
+                ("city", JSONSchemaDefine::string("City name")),
+                (
+                    "unit",
+                    JSONSchemaDefine::enum_of(&["celsius", "fahrenheit"]),
+                ),
+            ],
+            &["city"],
+        );
+        Function {
+            name: "get_weather".to_owned(),
+            description: None,
+            parameters: FunctionParameters {
+                schema_type: JSONSchemaType::Object,
+                properties,
+                required,
+            },
+        }
+    }
+
+    #[test]
+    fn validate_arguments_accepts_valid_arguments() {
+        let function = weather_function();
+        assert!(function.validate_arguments(r#"{"city": "Paris"}"#).is_ok());
+        assert!(function
+            .validate_arguments(r#"{"city": "Paris", "unit": "celsius"}"#)
+            .is_ok());
+    }
+
+    #[test]
+    fn validate_arguments_rejects_missing_required_field() {
+        match weather_function().validate_arguments(r#"{"unit": "celsius"}"#) {
+            Err(APIError::Unknown(err)) => {
+                assert!(
+                    err.starts_with("invalid arguments for get_weather"),
+                    "{}",
+                    err
+                );
+                assert!(err.contains("city"), "{}", err);
+            }
+            other => panic!("expected a missing field error, got {:?}", other),
+        }
+        assert!(weather_function()
+            .validate_arguments(r#"{"city": "Paris", "unit": "kelvin"}"#)
+            .is_err());
+        assert!(weather_function().validate_arguments("not json").is_err());
+    }
+
+    #[test]
+    fn audio_response_deserializes_and_decodes() {
+        let message: ChatCompletionMessageForResponse = serde_json::from_value(json!({
+            "role": "assistant",
+            "content": null,
+            "audio": {
+                "id": "audio_abc123",
+                "data": "aGVsbG8=",
+                "transcript": "hello",
+                "expires_at": 1729018505
+            }
+        }))
+        .unwrap();
+        let audio = message.audio.as_ref().unwrap();
+        assert_eq!(audio.id, "audio_abc123");
+        assert_eq!(audio.transcript, "hello");
+        assert_eq!(audio.expires_at, 1729018505);
+        assert_eq!(audio.decode().unwrap(), b"hello");
+
+        let request = serde_json::to_value(message.to_request_message()).unwrap();
+        assert!(request.get("audio").is_none());
+        assert_eq!(request["content"], "");
+    }
+
+    #[test]
+    fn audio_decode_rejects_invalid_base64() {
+        let audio = ChatAudio {
+            id: "audio_abc123".to_owned(),
+            data: "not base64!".to_owned(),
+            transcript: String::new(),
+            expires_at: 0,
+        };
+        assert!(matches!(audio.decode(), Err(APIError::Unknown(_))));
+    }
+
+    #[tokio::test]
+    async fn audio_from_path_serializes_input_audio_part() {
+        let path = std::env::temp_dir().join("openai-rst-input-audio.WAV");
+        std::fs::write(&path, b"hello").unwrap();
+        let content = Content::audio_from_path(&path.to_string_lossy()).await;
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            serde_json::to_value(content.unwrap()).unwrap(),
+            json!([{
+                "type": "input_audio",
+                "input_audio": { "data": "aGVsbG8=", "format": "wav" }
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn audio_from_path_rejects_unsupported_extension() {
+        match Content::audio_from_path("speech.ogg").await {
+            Err(APIError::Unknown(err)) => assert!(err.contains("speech.ogg"), "{}", err),
+            other => panic!("expected an unsupported format error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn to_request_message_keeps_tool_calls_of_null_content() {
+        let message: ChatCompletionMessageForResponse = serde_json::from_value(json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call_abc123",
+                "type": "function",
+                "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+            }]
+        }))
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(message.to_request_message()).unwrap(),
+            json!({
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [{
+                    "id": "call_abc123",
+                    "type": "function",
+                    "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_reasoning_effort_only_when_set() {
+        for (effort, wire) in [
+            (ReasoningEffort::Low, "low"),
+            (ReasoningEffort::Medium, "medium"),
+            (ReasoningEffort::High, "high"),
+        ] {
+            let body = serde_json::to_value(request().reasoning_effort(effort)).unwrap();
+            assert_eq!(body["reasoning_effort"], wire);
+        }
+        let body = serde_json::to_value(request()).unwrap();
+        assert!(body.get("reasoning_effort").is_none());
+    }
+
+    #[test]
+    fn serializes_store_and_metadata_only_when_set() {
+        let body = serde_json::to_value(request()).unwrap();
+        assert!(body.get("store").is_none() && body.get("metadata").is_none());
+
+        let metadata = HashMap::from([("user_id".to_owned(), "u_123".to_owned())]);
+        let body =
+            serde_json::to_value(request().store(true).metadata(metadata)).unwrap();
+        assert_eq!(body["store"], true);
+        assert_eq!(body["metadata"], json!({ "user_id": "u_123" }));
+    }
+
+    #[test]
+    fn deserializes_refusal_in_message_and_delta() {
+        let choice: ChatCompletionChoice = serde_json::from_value(json!({
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "refusal": "I can't help with that."
+            },
+            "finish_reason": "stop"
+        }))
+        .unwrap();
+        assert_eq!(choice.refusal(), Some("I can't help with that."));
+        assert!(choice.message.content.is_none());
+
+        let chunk = chunk(
+            json!([{ "index": 0, "delta": { "refusal": "I can't" } }]),
+            Value::Null,
+        );
+        assert_eq!(chunk.choices[0].delta.refusal.as_deref(), Some("I can't"));
+        assert!(chunk.choices[0].delta.content.is_none());
+    }
+
+    #[test]
+    fn serializes_prediction_only_when_set() {
+        let body = serde_json::to_value(request()).unwrap();
+        assert!(body.get("prediction").is_none());
+
+        let body = serde_json::to_value(
+            request().prediction(PredictionContent::new("fn main() {}".to_owned())),
+        )
+        .unwrap();
+        assert_eq!(
+            body["prediction"],
+            json!({ "type": "content", "content": "fn main() {}" })
+        );
+    }
+
+    fn response(system_fingerprint: Option<&str>) -> ChatCompletionResponse {
+        serde_json::from_value(json!({
+            "id": "chatcmpl-abc123",
+            "object": "chat.completion",
+            "created": 1694268190,
+            "model": "gpt-4o",
+            "choices": [],
+            "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 },
+            "system_fingerprint": system_fingerprint
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn same_backend_as_compares_fingerprints() {
+        let a = response(Some("fp_44709d6fcb"));
+        assert!(a.same_backend_as(&response(Some("fp_44709d6fcb"))));
+        assert!(!a.same_backend_as(&response(Some("fp_0123456789"))));
+        assert!(!a.same_backend_as(&response(None)));
+        assert!(!response(None).same_backend_as(&response(None)));
+    }
+
+    #[test]
+    fn push_methods_append_messages_in_order() {
+        let mut req = request();
+        req.push_assistant("Hi! How can I help?");
+        req.push_user("What's the weather?");
+        req.push_message(ChatCompletionMessage {
+            role: MessageRole::Tool,
+            content: Content::Text("Sunny".to_owned()),
+            name: None,
+            tool_call_id: Some("call_abc123".to_owned()),
+            tool_calls: None,
+        });
+
+        let roles: Vec<_> = req.messages.iter().map(|m| m.role.clone()).collect();
+        assert_eq!(
+            roles,
+            [
+                MessageRole::User,
+                MessageRole::Assistant,
+                MessageRole::User,
+                MessageRole::Tool
+            ]
+        );
+        assert_eq!(
+            req.messages[1].content,
+            Content::Text("Hi! How can I help?".to_owned())
+        );
+        assert_eq!(
+            req.messages[2].content,
+            Content::Text("What's the weather?".to_owned())
+        );
+        assert_eq!(req.messages[3].tool_call_id.as_deref(), Some("call_abc123"));
+    }
+
+    #[test]
+    fn serializes_stream_options_include_usage() {
+        let body = serde_json::to_value(request()).unwrap();
+        assert!(body.get("stream_options").is_none());
+
+        let body =
+            serde_json::to_value(request().stream(true).stream_options(StreamOptions {
+                include_usage: true,
+            }))
+            .unwrap();
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["stream_options"], json!({ "include_usage": true }));
+    }
+
+    #[test]
+    fn rejects_out_of_range_logit_bias() {
+        let req = request()
+            .with_logit_bias(HashMap::from([(50256, -100), (1234, 100)]))
+            .unwrap();
+        assert!(req.validate().is_ok());
+
+        match request().with_logit_bias(HashMap::from([(50256, 101)])) {
+            Err(APIError::Unknown(err)) => assert_eq!(
+                err,
+                "logit_bias for token 50256 must be between -100 and 100, got 101"
+            ),
+            other => panic!("expected a logit bias error, got {:?}", other.err()),
+        }
+
+        let req = request().logit_bias(HashMap::from([("50256".to_owned(), -101)]));
+        match req.validate() {
+            Err(APIError::Unknown(err)) => assert_eq!(
+                err,
+                "logit_bias for token 50256 must be between -100 and 100, got -101"
+            ),
+            other => panic!("expected a logit bias error, got {:?}", other),
+        }
+    }
+}
@@ -12,51 +12,357 @@ use crate::{
         AudioSpeechRequest, AudioSpeechResponse, AudioTranscriptionRequest,
         AudioTranscriptionResponse, AudioTranslationRequest, AudioTranslationResponse,
     },
-    chat_completion::{ChatCompletionRequest, ChatCompletionResponse},
+    batch::{BatchObject, CreateBatchRequest, ListBatch},
+    chat_completion::{
+        ChatCompletionMessage, ChatCompletionRequest, ChatCompletionResponse, ChatStream,
+    },
+    common::validate_metadata,
     completion::{CompletionRequest, CompletionResponse},
     edit::{EditRequest, EditResponse},
     embedding::{EmbeddingRequest, EmbeddingResponse},
     error::APIError,
     file::{
-        FileDeleteRequest, FileDeleteResponse, FileListResponse,
-        FileRetrieveContentRequest, FileRetrieveContentResponse, FileRetrieveRequest,
-        FileRetrieveResponse, FileUploadRequest, FileUploadResponse,
+        upload_form, FileDeleteRequest, FileDeleteResponse, FileListResponse,
+        FilePurpose, FileRetrieveContentRequest, FileRetrieveContentResponse,
+        FileRetrieveRequest, FileRetrieveResponse, FileUploadRequest, FileUploadResponse,
     },
     fine_tuning::{
-        CancelFineTuningJobRequest, CreateFineTuningJobRequest, FineTuningJobEvent,
-        FineTuningJobObject, FineTuningPagination, ListFineTuningJobEventsRequest,
-        RetrieveFineTuningJobRequest,
+        CancelFineTuningJobRequest, CreateFineTuningJobRequest, FineTuningCheckpoint,
+        FineTuningJobEvent, FineTuningJobObject, FineTuningPagination,
+        ListFineTuningJobEventsRequest, RetrieveFineTuningJobRequest,
     },
     image::{
         ImageEditRequest, ImageEditResponse, ImageGenerationRequest,
         ImageGenerationResponse, ImageVariationRequest, ImageVariationResponse,
     },
+    impl_builder_methods,
     message::{
         CreateMessageRequest, ListMessage, ListMessageFile, MessageFileObject,
         MessageObject, ModifyMessageRequest,
     },
+    models::Model,
     moderation::{CreateModerationRequest, CreateModerationResponse},
+    ratelimit::RateLimitInfo,
+    responses::{CreateResponseRequest, ResponseObject},
     run::{
         CreateRunRequest, CreateThreadAndRunRequest, ListRun, ListRunStep,
-        ModifyRunRequest, RunObject, RunStepObject,
+        ModifyRunRequest, RunObject, RunPollBackoff, RunStepObject, RunStreamEvent,
     },
+    stream::{sse_events, sse_stream, with_read_timeout},
     thread::{CreateThreadRequest, ModifyThreadRequest, ThreadObject},
+    vector_store::{
+        CreateVectorStoreFileRequest, CreateVectorStoreRequest, ListVectorStore,
+        VectorStoreFileObject, VectorStoreObject,
+    },
 };
 use async_std::{
     fs::{create_dir_all, File},
     io::WriteExt,
 };
+use futures_util::{Stream, StreamExt};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use reqwest::{
-    header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
-    Client as ReqwestClient, Response,
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT},
+    multipart::Form,
+    Client as ReqwestClient, Method, Proxy, RequestBuilder, Response, StatusCode,
 };
-use std::path::Path;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio_util::sync::CancellationToken;
 
 const API_URL_V1: &str = "https://api.openai.com/v1";
 
+const DEFAULT_USER_AGENT: &str = concat!("openai-rst/", env!("CARGO_PKG_VERSION"));
+
+/// Characters escaped in query parameters: everything except the RFC 3986 unreserved set.
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+const DEFAULT_ASSISTANTS_BETA: &str = "assistants=v2";
+
+/// Path prefixes of the endpoints that require the `OpenAI-Beta` header.
+const BETA_PATHS: &[&str] = &["/assistants", "/threads", "/vector_stores"];
+
 /// Result type alias for client operations.
 type ClientResult<T> = Result<T, APIError>;
 
+/// Per-request overrides applied on top of the client's headers.
+///
+/// Headers set here take precedence over the headers configured on the `Client` and on the
+/// underlying `reqwest` client, so a per-request organization always wins.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Optional organization to bill the request to, sent as `OpenAI-Organization`.
+    pub organization: Option<String>,
+    /// Optional project to attribute the request to, sent as `OpenAI-Project`.
+    pub project: Option<String>,
+    /// Optional key sent as `Idempotency-Key`, so retrying a create request with the same
+    /// options does not create a duplicate object.
+    pub idempotency_key: Option<String>,
+}
+
+impl RequestOptions {
+    /// Creates a new, empty `RequestOptions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a freshly generated random UUID as the idempotency key.
+    ///
+    /// Keep the returned options and reuse them for every retry of the same operation; a new
+    /// key per attempt would defeat deduplication.
+    pub fn with_generated_idempotency_key(self) -> Self {
+        let bits = fastrand::u128(..) & !(0xf000 << 64) | (0x4000 << 64);
+        let bits = bits & !(0xc << 60) | (0x8 << 60);
+        let hex = format!("{:032x}", bits);
+        self.idempotency_key(format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        ))
+    }
+
+    /// Builds the headers carrying these overrides.
+    fn headers(&self) -> ClientResult<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        if let Some(organization) = &self.organization {
+            headers.insert("OpenAI-Organization", HeaderValue::from_str(organization)?);
+        }
+        if let Some(project) = &self.project {
+            headers.insert("OpenAI-Project", HeaderValue::from_str(project)?);
+        }
+        if let Some(idempotency_key) = &self.idempotency_key {
+            headers.insert("Idempotency-Key", HeaderValue::from_str(idempotency_key)?);
+        }
+        Ok(headers)
+    }
+}
+
+impl_builder_methods!(
+    RequestOptions,
+    organization: String,
+    project: String,
+    idempotency_key: String
+);
+
+/// Represents an API payload along with the transport metadata of the response carrying it.
+#[derive(Debug)]
+pub struct ApiResponse<T> {
+    /// Deserialized payload of the response.
+    pub data: T,
+    /// Optional request ID from the `x-request-id` header, useful when contacting support.
+    pub request_id: Option<String>,
+    /// Optional rate limit state from the `x-ratelimit-*` headers.
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+/// Settings for routing requests to an Azure OpenAI resource.
+#[derive(Debug, Clone)]
+pub struct AzureConfig {
+    /// API version sent as the `api-version` query parameter.
+    pub api_version: String,
+}
+
+/// Builder for configuring and constructing a `Client`.
+///
+/// Authentication and `User-Agent` headers are layered onto each request rather than set as
+/// default headers, so they also apply to an HTTP client supplied with `with_http_client`.
+/// Per-request `RequestOptions` take precedence over these headers, which in turn take
+/// precedence over any default headers of the supplied HTTP client.
+///
+/// `Content-Type` is never a client-wide header: each request sets it from its body, so JSON
+/// requests send `application/json` and multipart uploads send their form boundary.
+pub struct ClientBuilder {
+    /// API key for authentication.
+    api_key: String,
+    /// API endpoint URL.
+    endpoint: String,
+    /// Optional Azure OpenAI settings.
+    azure: Option<AzureConfig>,
+    /// `User-Agent` header sent with every request.
+    user_agent: String,
+    /// Optional preconfigured HTTP client to send requests with.
+    http_client: Option<ReqwestClient>,
+    /// Proxies applied when constructing the HTTP client.
+    proxies: Vec<Proxy>,
+    /// Optional `OpenAI-Beta` header sent to the assistants endpoints.
+    assistants_beta: Option<String>,
+    /// Model used by chat requests that do not name one.
+    default_model: Model,
+    /// Whether to wait for exhausted rate limits to reset before sending requests.
+    pace_rate_limits: bool,
+    /// Optional overall timeout of non-streaming requests.
+    timeout: Option<Duration>,
+    /// Optional timeout for establishing connections.
+    connect_timeout: Option<Duration>,
+    /// Optional timeout for each read of a response.
+    read_timeout: Option<Duration>,
+}
+
+impl ClientBuilder {
+    /// Creates a new `ClientBuilder` with the given API key and the default endpoint.
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            endpoint: API_URL_V1.to_owned(),
+            azure: None,
+            user_agent: DEFAULT_USER_AGENT.to_owned(),
+            http_client: None,
+            proxies: Vec::new(),
+            assistants_beta: Some(DEFAULT_ASSISTANTS_BETA.to_owned()),
+            default_model: Model::default(),
+            pace_rate_limits: false,
+            timeout: None,
+            connect_timeout: None,
+            read_timeout: None,
+        }
+    }
+
+    /// Sets the API endpoint URL.
+    pub fn endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Routes requests to an Azure OpenAI resource using the given API version.
+    pub fn azure(mut self, api_version: String) -> Self {
+        self.azure = Some(AzureConfig { api_version });
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Sends requests with a preconfigured HTTP client, sharing its connection pool.
+    pub fn with_http_client(mut self, http_client: ReqwestClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Routes requests through the given proxy; may be called repeatedly to add several.
+    ///
+    /// Proxy credentials can be supplied with `Proxy::basic_auth` or in the proxy URL. Proxies
+    /// are ignored when an HTTP client is supplied with `with_http_client`, which should be
+    /// configured with its own proxies instead.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Sets the `OpenAI-Beta` header sent to the assistants, threads and vector stores
+    /// endpoints, defaulting to `assistants=v2`; `None` stops sending it.
+    pub fn assistants_beta(mut self, assistants_beta: Option<String>) -> Self {
+        self.assistants_beta = assistants_beta;
+        self
+    }
+
+    /// Sets the model used by chat requests that do not name one, defaulting to `Model::default()`.
+    pub fn default_model(mut self, default_model: Model) -> Self {
+        self.default_model = default_model;
+        self
+    }
+
+    /// Waits for an exhausted request or token limit to reset before sending the next request,
+    /// based on the `x-ratelimit-*` headers of the previous response.
+    pub fn pace_rate_limits(mut self, pace_rate_limits: bool) -> Self {
+        self.pace_rate_limits = pace_rate_limits;
+        self
+    }
+
+    /// Sets the overall timeout of non-streaming requests, from sending the request to reading
+    /// the whole response.
+    ///
+    /// Streaming requests are not bound by it, as they may legitimately last for minutes; use
+    /// `read_timeout` to guard them against stalls instead.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    ///
+    /// Ignored when an HTTP client is supplied with `with_http_client`, which should be
+    /// configured with its own connect timeout instead.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets how long to wait for the response to start, and then for each chunk of a streamed
+    /// response, before failing with `APIError::Timeout`.
+    pub fn read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = Some(read_timeout);
+        self
+    }
+
+    /// Builds the `Client`.
+    pub fn build(self) -> ClientResult<Client> {
+        let mut headers = HeaderMap::new();
+        match self.azure {
+            Some(_) => {
+                headers.insert("api-key", HeaderValue::from_str(&self.api_key)?);
+            }
+            None => {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {}", self.api_key))?,
+                );
+            }
+        }
+        headers.insert(USER_AGENT, HeaderValue::from_str(&self.user_agent)?);
+
+        let mut beta_headers = HeaderMap::new();
+        if let Some(assistants_beta) = &self.assistants_beta {
+            beta_headers.insert("OpenAI-Beta", HeaderValue::from_str(assistants_beta)?);
+        }
+
+        let client = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = self
+                    .proxies
+                    .into_iter()
+                    .fold(ReqwestClient::builder(), |builder, proxy| {
+                        builder.proxy(proxy)
+                    });
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            endpoint: self.endpoint,
+            api_key: self.api_key,
+            client,
+            azure: self.azure,
+            headers,
+            beta_headers,
+            default_model: self.default_model,
+            pace_rate_limits: self.pace_rate_limits,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            rate_limit: Arc::default(),
+        })
+    }
+}
+
 /// The `Client` struct for interacting with the OpenAI API.
 pub struct Client {
     /// API endpoint URL.
@@ -65,115 +371,381 @@ pub struct Client {
     pub api_key: String,
     /// Reqwest client for making HTTP requests.
     pub client: ReqwestClient,
+    /// Optional Azure OpenAI settings, set when the client targets an Azure resource.
+    pub azure: Option<AzureConfig>,
+    /// Headers layered onto every request, such as authentication.
+    pub headers: HeaderMap,
+    /// Headers layered onto requests to beta endpoints, such as `OpenAI-Beta`.
+    pub beta_headers: HeaderMap,
+    /// Model used by chat requests that do not name one.
+    pub default_model: Model,
+    /// Whether to wait for exhausted rate limits to reset before sending requests.
+    pub pace_rate_limits: bool,
+    /// Optional overall timeout of non-streaming requests.
+    pub timeout: Option<Duration>,
+    /// Optional timeout for establishing a connection, applied to the HTTP client built by
+    /// `ClientBuilder::build`.
+    pub connect_timeout: Option<Duration>,
+    /// Optional timeout for the response to start, and for each chunk of a streamed response.
+    pub read_timeout: Option<Duration>,
+    /// Rate limit state of the latest response, with the time it was received.
+    rate_limit: Arc<Mutex<Option<(RateLimitInfo, Instant)>>>,
 }
 
 impl Client {
+    /// Creates a new `ClientBuilder` with the given API key.
+    pub fn builder(api_key: String) -> ClientBuilder {
+        ClientBuilder::new(api_key)
+    }
+
     /// Creates a new `Client` instance from environment variables.
+    ///
+    /// Returns `APIError::MissingApiKey` if `OPENAI_API_KEY` is not set.
     pub fn from_env() -> ClientResult<Self> {
-        let endpoint =
-            std::env::var("OPENAI_API_BASE").unwrap_or_else(|_| API_URL_V1.to_owned());
-        let api_key = std::env::var("OPENAI_API_KEY").expect("OPENAI_API_KEY is not set");
-        let mut headers = HeaderMap::new();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", api_key))?,
-        );
-
-        let client = ReqwestClient::builder().default_headers(headers).build()?;
+        Self::from_env_with(|name| std::env::var(name).ok())
+    }
 
-        Ok(Self {
-            endpoint,
-            api_key,
-            client,
-        })
+    /// Creates a new `Client` reading configuration through `lookup` instead of the process
+    /// environment.
+    fn from_env_with(lookup: impl Fn(&str) -> Option<String>) -> ClientResult<Self> {
+        let endpoint = lookup("OPENAI_API_BASE").unwrap_or_else(|| API_URL_V1.to_owned());
+        let api_key = lookup("OPENAI_API_KEY")
+            .ok_or_else(|| APIError::MissingApiKey("OPENAI_API_KEY".to_owned()))?;
+        ClientBuilder::new(api_key).endpoint(endpoint).build()
     }
 
     /// Creates a new `Client` instance with the given API key.
     pub fn new(api_key: String) -> ClientResult<Self> {
-        let mut headers = HeaderMap::new();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", api_key))?,
-        );
+        ClientBuilder::new(api_key).build()
+    }
 
-        let client = ReqwestClient::builder().default_headers(headers).build()?;
+    /// Creates a new `Client` targeting an Azure OpenAI resource.
+    ///
+    /// Requests are routed to `{endpoint}/openai/deployments/{model}/...`, so the `model` of
+    /// each request must name an Azure deployment.
+    pub fn azure(
+        endpoint: String,
+        api_key: String,
+        api_version: String,
+    ) -> ClientResult<Self> {
+        ClientBuilder::new(api_key)
+            .endpoint(endpoint)
+            .azure(api_version)
+            .build()
+    }
 
-        Ok(Self {
-            endpoint: API_URL_V1.to_owned(),
-            api_key,
-            client,
-        })
+    /// Returns the rate limit state reported by the latest response, if any.
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(info, _)| info.clone())
     }
 
-    /// Constructs a full API path from a given endpoint path.
-    fn from_path(p: &str) -> String {
-        format!("{}{}", API_URL_V1, p)
+    /// Returns how long to wait for an exhausted rate limit to reset, if at all.
+    fn rate_limit_wait(&self) -> Option<Duration> {
+        let rate_limit = self.rate_limit.lock().unwrap();
+        let (info, received_at) = rate_limit.as_ref()?;
+        info.wait_time(received_at.elapsed())
+    }
+
+    /// Starts a request to the given path with the client's headers applied, adding the beta
+    /// headers for the endpoints that require them.
+    ///
+    /// For Azure clients, `deployment` selects the deployment the path is nested under.
+    fn request(
+        &self,
+        method: Method,
+        path: &str,
+        deployment: Option<&str>,
+    ) -> RequestBuilder {
+        let builder = self
+            .client
+            .request(method, self.url(path, deployment))
+            .headers(self.headers.clone());
+        if BETA_PATHS.iter().any(|prefix| path.starts_with(prefix)) {
+            builder.headers(self.beta_headers.clone())
+        } else {
+            builder
+        }
+    }
+
+    /// Sends a prepared request bound by the client's overall timeout.
+    async fn send(&self, builder: RequestBuilder) -> ClientResult<Response> {
+        self.execute(builder, self.timeout).await
+    }
+
+    /// Sends a prepared streaming request, returning its body as a stream of chunks bound by
+    /// the read timeout rather than the overall timeout.
+    async fn post_stream<T: Serialize>(
+        &self,
+        path: &str,
+        params: &T,
+    ) -> ClientResult<impl Stream<Item = ClientResult<impl AsRef<[u8]>>>> {
+        let deployment = self.deployment(params)?;
+        let builder = self
+            .request(Method::POST, path, deployment.as_deref())
+            .json(params);
+        let response = Client::check_status(self.execute(builder, None).await?).await?;
+        Ok(with_read_timeout(
+            response.bytes_stream(),
+            self.read_timeout,
+        ))
+    }
+
+    /// Sends a prepared request with the given overall timeout, emitting `debug!` events for
+    /// the request and the response status when the `tracing` feature is enabled.
+    ///
+    /// Without a timeout, the default timeout of the underlying HTTP client applies.
+    async fn execute(
+        &self,
+        builder: RequestBuilder,
+        timeout: Option<Duration>,
+    ) -> ClientResult<Response> {
+        let mut request = builder.build()?;
+        if timeout.is_some() {
+            *request.timeout_mut() = timeout;
+        }
+        if self.pace_rate_limits {
+            if let Some(wait) = self.rate_limit_wait() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        #[cfg(feature = "tracing")]
+        log_request(&request);
+        let response = match self.read_timeout {
+            Some(read_timeout) => {
+                tokio::time::timeout(read_timeout, self.client.execute(request))
+                    .await
+                    .map_err(|_| APIError::Timeout(read_timeout))??
+            }
+            None => self.client.execute(request).await?,
+        };
+        if let Some(info) = RateLimitInfo::from_headers(response.headers()) {
+            *self.rate_limit.lock().unwrap() = Some((info, Instant::now()));
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            status = response.status().as_u16(),
+            url = %response.url(),
+            "received OpenAI response"
+        );
+        Ok(response)
+    }
+
+    /// Constructs a full API URL from a given endpoint path.
+    ///
+    /// For Azure clients, `deployment` selects the deployment the path is nested under.
+    fn url(&self, p: &str, deployment: Option<&str>) -> String {
+        match &self.azure {
+            None => format!("{}{}", self.endpoint, p),
+            Some(azure) => {
+                let separator = if p.contains('?') { '&' } else { '?' };
+                let prefix = match deployment {
+                    Some(deployment) => format!("/openai/deployments/{}", deployment),
+                    None => "/openai".to_owned(),
+                };
+                format!(
+                    "{}{}{}{}api-version={}",
+                    self.endpoint, prefix, p, separator, azure.api_version
+                )
+            }
+        }
+    }
+
+    /// Returns the deployment to route a request body to, taken from its `model` field.
+    fn deployment<T: Serialize>(&self, params: &T) -> ClientResult<Option<String>> {
+        if self.azure.is_none() {
+            return Ok(None);
+        }
+        let params = serde_json::to_value(params)?;
+        Ok(params
+            .get("model")
+            .and_then(|model| model.as_str())
+            .map(str::to_owned))
     }
 
     /// Sends a POST request with the given path and parameters.
-    pub async fn post<T: serde::ser::Serialize>(
+    pub async fn post<T: Serialize>(
         &self,
         path: &str,
         params: &T,
     ) -> ClientResult<Response> {
-        let url = Client::from_path(path);
-        self.client
-            .post(&url)
-            .json(params)
-            .send()
+        self.post_with_opts(path, params, &RequestOptions::default())
             .await
-            .map_err(APIError::ReqwestError)
+    }
+
+    /// Sends a POST request with the given path, parameters, and per-request overrides.
+    pub async fn post_with_opts<T: Serialize>(
+        &self,
+        path: &str,
+        params: &T,
+        opts: &RequestOptions,
+    ) -> ClientResult<Response> {
+        let deployment = self.deployment(params)?;
+        let builder = self
+            .request(Method::POST, path, deployment.as_deref())
+            .headers(opts.headers()?)
+            .json(params);
+        self.send(builder).await
     }
 
     /// Sends a GET request to the given path.
     pub async fn get(&self, path: &str) -> ClientResult<Response> {
-        let url = Client::from_path(path);
-        self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.send(self.request(Method::GET, path, None)).await
     }
 
     /// Sends a DELETE request to the given path.
     pub async fn delete(&self, path: &str) -> ClientResult<Response> {
-        let url = Client::from_path(path);
-        self.client
-            .delete(&url)
-            .send()
+        self.send(self.request(Method::DELETE, path, None)).await
+    }
+
+    /// Sends a POST request and deserializes the response, returning an error on a non-success status.
+    pub async fn post_json<Req: Serialize, Resp: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &Req,
+    ) -> ClientResult<Resp> {
+        self.post_json_with_opts(path, params, &RequestOptions::default())
+            .await
+    }
+
+    /// Sends a POST request with per-request overrides and deserializes the response,
+    /// returning an error on a non-success status.
+    async fn post_json_with_opts<Req: Serialize, Resp: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &Req,
+        opts: &RequestOptions,
+    ) -> ClientResult<Resp> {
+        let response = self.post_with_opts(path, params, opts).await?;
+        Client::json_response(response).await
+    }
+
+    /// Sends a GET request and deserializes the response, returning an error on a non-success status.
+    pub async fn get_json<Resp: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> ClientResult<Resp> {
+        let response = self.get(path).await?;
+        Client::json_response(response).await
+    }
+
+    /// Sends a DELETE request and deserializes the response, returning an error on a non-success status.
+    pub async fn delete_json<Resp: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> ClientResult<Resp> {
+        let response = self.delete(path).await?;
+        Client::json_response(response).await
+    }
+
+    /// Sends a DELETE request, using `on_empty` when the API answers `204 No Content` or an empty body.
+    async fn delete_json_or_else<Resp, F>(
+        &self,
+        path: &str,
+        on_empty: F,
+    ) -> ClientResult<Resp>
+    where
+        Resp: DeserializeOwned,
+        F: FnOnce() -> Resp,
+    {
+        let response = Client::check_status(self.delete(path).await?).await?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(on_empty());
+        }
+        let bytes = response.bytes().await?;
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return Ok(on_empty());
+        }
+        serde_json::from_slice(&bytes).map_err(APIError::SerdeError)
+    }
+
+    /// Returns the response if its status is a success, or an `APIError::ApiError` built from the error body.
+    async fn check_status(response: Response) -> ClientResult<Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        Err(APIError::from_response(response).await)
+    }
+
+    /// Checks the response status and deserializes the body into the expected type.
+    async fn json_response<T: DeserializeOwned>(response: Response) -> ClientResult<T> {
+        Client::check_status(response)
+            .await?
+            .json::<T>()
             .await
             .map_err(APIError::ReqwestError)
     }
 
+    /// Checks the response status and deserializes the body, keeping the request ID and rate
+    /// limit state of the response.
+    async fn json_response_meta<T: DeserializeOwned>(
+        response: Response,
+    ) -> ClientResult<ApiResponse<T>> {
+        let response = Client::check_status(response).await?;
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let rate_limit = RateLimitInfo::from_headers(response.headers());
+        let data = response.json::<T>().await.map_err(APIError::ReqwestError)?;
+        Ok(ApiResponse {
+            data,
+            request_id,
+            rate_limit,
+        })
+    }
+
+    /// Verifies the API key and connectivity with a cheap `GET /models` request.
+    ///
+    /// Returns `Ok(true)` when the API accepts the key, and an `APIError::ApiError` carrying
+    /// the status otherwise, such as `401` for an invalid key.
+    pub async fn verify_key(&self) -> ClientResult<bool> {
+        Client::check_status(self.get("/models").await?).await?;
+        Ok(true)
+    }
+
     /// Sends a completion request and returns the response.
     pub async fn completion(
         &self,
         req: CompletionRequest,
     ) -> ClientResult<CompletionResponse> {
-        let url = Client::from_path("/completions");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<CompletionResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json("/completions", &req).await
+    }
+
+    /// Sends a streaming completion request and returns a stream of incremental responses.
+    pub async fn completion_stream(
+        &self,
+        req: CompletionRequest,
+    ) -> ClientResult<impl Stream<Item = ClientResult<CompletionResponse>>> {
+        let req = req.stream(true);
+        Ok(sse_stream(self.post_stream("/completions", &req).await?))
+    }
+
+    /// Sends a completion request and returns the response with its metadata.
+    pub async fn completion_meta(
+        &self,
+        req: CompletionRequest,
+    ) -> ClientResult<ApiResponse<CompletionResponse>> {
+        Client::json_response_meta(self.post("/completions", &req).await?).await
     }
 
     /// Sends an edit request and returns the response.
+    ///
+    /// The `/edits` endpoint is deprecated; prefer `edit_via_chat`.
     pub async fn edit(&self, req: EditRequest) -> ClientResult<EditResponse> {
-        let url = Client::from_path("/edits");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<EditResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json("/edits", &req).await
+    }
+
+    /// Runs an edit request as a chat completion and returns the result as an edit response.
+    ///
+    /// This is the recommended way to perform edits, as the `/edits` endpoint is deprecated.
+    pub async fn edit_via_chat(&self, req: EditRequest) -> ClientResult<EditResponse> {
+        let response = self.chat_completion(req.to_chat_request()).await?;
+        Ok(EditResponse::from_chat_response(response))
     }
 
     /// Sends an image generation request and returns the response.
@@ -181,15 +753,8 @@ impl Client {
         &self,
         req: ImageGenerationRequest,
     ) -> ClientResult<ImageGenerationResponse> {
-        let url = Client::from_path("/images/generations");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<ImageGenerationResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        req.validate()?;
+        self.post_json("/images/generations", &req).await
     }
 
     /// Sends an image edit request and returns the response.
@@ -197,15 +762,13 @@ impl Client {
         &self,
         req: ImageEditRequest,
     ) -> ClientResult<ImageEditResponse> {
-        let url = Client::from_path("/images/edits");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<ImageEditResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let deployment = self.deployment(&req)?;
+        let form = req.form().await?;
+        let builder = self
+            .request(Method::POST, "/images/edits", deployment.as_deref())
+            .multipart(form);
+        let response = self.send(builder).await?;
+        Client::json_response(response).await
     }
 
     /// Sends an image variation request and returns the response.
@@ -213,15 +776,13 @@ impl Client {
         &self,
         req: ImageVariationRequest,
     ) -> ClientResult<ImageVariationResponse> {
-        let url = Client::from_path("/images/variations");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<ImageVariationResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let deployment = self.deployment(&req)?;
+        let form = req.form().await?;
+        let builder = self
+            .request(Method::POST, "/images/variations", deployment.as_deref())
+            .multipart(form);
+        let response = self.send(builder).await?;
+        Client::json_response(response).await
     }
 
     /// Sends an embedding request and returns the response.
@@ -229,27 +790,52 @@ impl Client {
         &self,
         req: EmbeddingRequest,
     ) -> ClientResult<EmbeddingResponse> {
-        let url = Client::from_path("/embeddings");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<EmbeddingResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        req.validate()?;
+        self.post_json("/embeddings", &req).await
+    }
+
+    /// Sends an embedding request and returns the response with its metadata.
+    pub async fn embedding_meta(
+        &self,
+        req: EmbeddingRequest,
+    ) -> ClientResult<ApiResponse<EmbeddingResponse>> {
+        req.validate()?;
+        Client::json_response_meta(self.post("/embeddings", &req).await?).await
     }
 
     /// Retrieves a list of files.
     pub async fn file_list(&self) -> ClientResult<FileListResponse> {
-        let url = Client::from_path("/files");
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<FileListResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.file_list_filtered(None).await
+    }
+
+    /// Retrieves a list of files, optionally narrowed to a single purpose.
+    pub async fn file_list_filtered(
+        &self,
+        purpose: Option<FilePurpose>,
+    ) -> ClientResult<FileListResponse> {
+        self.file_list_page(None, None, None, purpose).await
+    }
+
+    /// Retrieves a page of files, optionally narrowed to a single purpose.
+    ///
+    /// Pass the `last_id` of the previous page as `after` to fetch the next one. Returns an
+    /// error without sending the request if `purpose` is `FilePurpose::Other`.
+    pub async fn file_list_page(
+        &self,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        purpose: Option<FilePurpose>,
+    ) -> ClientResult<FileListResponse> {
+        if let Some(purpose) = &purpose {
+            purpose.validate()?;
+        }
+        let extra = purpose
+            .map(|purpose| vec![("purpose", purpose.to_string())])
+            .unwrap_or_default();
+        let path =
+            Client::query_params(limit, order, after, None, extra, "/files".to_owned());
+        self.get_json(&path).await
     }
 
     /// Uploads a file and returns the response.
@@ -257,15 +843,26 @@ impl Client {
         &self,
         req: FileUploadRequest,
     ) -> ClientResult<FileUploadResponse> {
-        let url = Client::from_path("/files");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<FileUploadResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let form = req.form().await?;
+        self.upload_file_form(form).await
+    }
+
+    /// Uploads in-memory data as a file named `filename`, without writing it to disk first.
+    pub async fn file_upload_bytes(
+        &self,
+        bytes: Vec<u8>,
+        filename: String,
+        purpose: FilePurpose,
+    ) -> ClientResult<FileUploadResponse> {
+        let form = upload_form(bytes, filename, &purpose)?;
+        self.upload_file_form(form).await
+    }
+
+    /// Sends a multipart file upload and returns the response.
+    async fn upload_file_form(&self, form: Form) -> ClientResult<FileUploadResponse> {
+        let builder = self.request(Method::POST, "/files", None).multipart(form);
+        let response = self.send(builder).await?;
+        Client::json_response(response).await
     }
 
     /// Deletes a file and returns the response.
@@ -274,14 +871,8 @@ impl Client {
         req: FileDeleteRequest,
     ) -> ClientResult<FileDeleteResponse> {
         let path = format!("/files/{}", req.file_id);
-        let url = Client::from_path(&path);
-        self.client
-            .delete(&url)
-            .send()
-            .await?
-            .json::<FileDeleteResponse>()
+        self.delete_json_or_else(&path, || FileDeleteResponse::deleted(req.file_id))
             .await
-            .map_err(APIError::ReqwestError)
     }
 
     /// Retrieves a file's metadata and returns the response.
@@ -290,14 +881,7 @@ impl Client {
         req: FileRetrieveRequest,
     ) -> ClientResult<FileRetrieveResponse> {
         let path = format!("/files/{}", req.file_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<FileRetrieveResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Retrieves the content of a file and returns the response.
@@ -306,14 +890,7 @@ impl Client {
         req: FileRetrieveContentRequest,
     ) -> ClientResult<FileRetrieveContentResponse> {
         let path = format!("/files/{}/content", req.file_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<FileRetrieveContentResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Sends a chat completion request and returns the response.
@@ -321,15 +898,86 @@ impl Client {
         &self,
         req: ChatCompletionRequest,
     ) -> ClientResult<ChatCompletionResponse> {
-        let url = Client::from_path("/chat/completions");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<ChatCompletionResponse>()
+        self.chat_completion_with_opts(req, &RequestOptions::default())
             .await
-            .map_err(APIError::ReqwestError)
+    }
+
+    /// Sends a chat completion request with per-request overrides and returns the response.
+    ///
+    /// A request with an empty `model` is sent with the client's default model.
+    pub async fn chat_completion_with_opts(
+        &self,
+        req: ChatCompletionRequest,
+        opts: &RequestOptions,
+    ) -> ClientResult<ChatCompletionResponse> {
+        Client::json_response(self.send_chat(req, opts).await?).await
+    }
+
+    /// Sends a chat completion request and returns the response with its metadata.
+    pub async fn chat_completion_meta(
+        &self,
+        req: ChatCompletionRequest,
+    ) -> ClientResult<ApiResponse<ChatCompletionResponse>> {
+        let response = self.send_chat(req, &RequestOptions::default()).await?;
+        Client::json_response_meta(response).await
+    }
+
+    /// Validates and sends a chat completion request, filling an empty `model` with the
+    /// client's default model.
+    async fn send_chat(
+        &self,
+        mut req: ChatCompletionRequest,
+        opts: &RequestOptions,
+    ) -> ClientResult<Response> {
+        req.validate()?;
+        if req.model.is_empty() {
+            req.model = self.default_model.to_string();
+        }
+        self.post_with_opts("/chat/completions", &req, opts).await
+    }
+
+    /// Sends a chat completion request that is abandoned with `APIError::Cancelled` as soon as
+    /// `cancel` is triggered.
+    ///
+    /// Dropping the future of any request also cancels it; this variant suits callers that
+    /// signal cancellation from elsewhere, such as a UI back button.
+    pub async fn chat_completion_with_cancel(
+        &self,
+        req: ChatCompletionRequest,
+        cancel: CancellationToken,
+    ) -> ClientResult<ChatCompletionResponse> {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => Err(APIError::Cancelled),
+            response = self.chat_completion(req) => response,
+        }
+    }
+
+    /// Sends a streaming chat completion request and returns a stream of incremental chunks.
+    ///
+    /// Set `include_usage` in the request's `stream_options` to receive a final chunk with the
+    /// token usage of the whole request.
+    pub async fn chat_completion_stream(
+        &self,
+        mut req: ChatCompletionRequest,
+    ) -> ClientResult<ChatStream> {
+        req.validate()?;
+        if req.model.is_empty() {
+            req.model = self.default_model.to_string();
+        }
+        let req = req.stream(true);
+        Ok(ChatStream::new(sse_stream(
+            self.post_stream("/chat/completions", &req).await?,
+        )))
+    }
+
+    /// Sends the given messages to the client's default model and returns the response.
+    pub async fn chat(
+        &self,
+        messages: Vec<ChatCompletionMessage>,
+    ) -> ClientResult<ChatCompletionResponse> {
+        let req = ChatCompletionRequest::new_multi(self.default_model.clone(), messages);
+        self.chat_completion(req).await
     }
 
     /// Sends an audio transcription request and returns the response.
@@ -337,15 +985,13 @@ impl Client {
         &self,
         req: AudioTranscriptionRequest,
     ) -> ClientResult<AudioTranscriptionResponse> {
-        let url = Client::from_path("/audio/transcriptions");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<AudioTranscriptionResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let deployment = self.deployment(&req)?;
+        let form = req.form().await?;
+        let builder = self
+            .request(Method::POST, "/audio/transcriptions", deployment.as_deref())
+            .multipart(form);
+        let response = self.send(builder).await?;
+        Client::json_response(response).await
     }
 
     /// Sends an audio translation request and returns the response.
@@ -353,15 +999,13 @@ impl Client {
         &self,
         req: AudioTranslationRequest,
     ) -> ClientResult<AudioTranslationResponse> {
-        let url = Client::from_path("/audio/translations");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<AudioTranslationResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let deployment = self.deployment(&req)?;
+        let form = req.form().await?;
+        let builder = self
+            .request(Method::POST, "/audio/translations", deployment.as_deref())
+            .multipart(form);
+        let response = self.send(builder).await?;
+        Client::json_response(response).await
     }
 
     /// Sends an audio speech request, saves the response to a file, and returns the response.
@@ -369,8 +1013,11 @@ impl Client {
         &self,
         req: AudioSpeechRequest,
     ) -> ClientResult<AudioSpeechResponse> {
-        let url = Client::from_path("/audio/speech");
-        let response = self.client.post(&url).json(&req).send().await?;
+        let deployment = self.deployment(&req)?;
+        let builder = self
+            .request(Method::POST, "/audio/speech", deployment.as_deref())
+            .json(&req);
+        let response = Client::check_status(self.send(builder).await?).await?;
 
         let bytes = response.bytes().await?;
         let path = Path::new(&req.output);
@@ -389,29 +1036,26 @@ impl Client {
         &self,
         req: CreateFineTuningJobRequest,
     ) -> ClientResult<FineTuningJobObject> {
-        let url = Client::from_path("/fine_tuning/jobs");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<FineTuningJobObject>()
+        self.create_fine_tuning_job_with_opts(req, &RequestOptions::default())
+            .await
+    }
+
+    /// Creates a fine-tuning job with per-request overrides, such as an idempotency key, and
+    /// returns the response.
+    pub async fn create_fine_tuning_job_with_opts(
+        &self,
+        req: CreateFineTuningJobRequest,
+        opts: &RequestOptions,
+    ) -> ClientResult<FineTuningJobObject> {
+        self.post_json_with_opts("/fine_tuning/jobs", &req, opts)
             .await
-            .map_err(APIError::ReqwestError)
     }
 
     /// Lists fine-tuning jobs and returns the response.
     pub async fn list_fine_tuning_jobs(
         &self,
     ) -> ClientResult<FineTuningPagination<FineTuningJobObject>> {
-        let url = Client::from_path("/fine_tuning/jobs");
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<FineTuningPagination<FineTuningJobObject>>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json("/fine_tuning/jobs").await
     }
 
     /// Lists fine-tuning job events and returns the response.
@@ -420,14 +1064,19 @@ impl Client {
         req: ListFineTuningJobEventsRequest,
     ) -> ClientResult<FineTuningPagination<FineTuningJobEvent>> {
         let path = format!("/fine_tuning/jobs/{}/events", req.fine_tuning_job_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<FineTuningPagination<FineTuningJobEvent>>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
+    }
+
+    /// Lists the checkpoints of a fine-tuning job and returns the response.
+    pub async fn list_fine_tuning_checkpoints(
+        &self,
+        job_id: String,
+        limit: Option<i64>,
+        after: Option<String>,
+    ) -> ClientResult<FineTuningPagination<FineTuningCheckpoint>> {
+        let path = format!("/fine_tuning/jobs/{}/checkpoints", job_id);
+        let path = Client::query_params(limit, None, after, None, Vec::new(), path);
+        self.get_json(&path).await
     }
 
     /// Retrieves a fine-tuning job and returns the response.
@@ -436,14 +1085,7 @@ impl Client {
         req: RetrieveFineTuningJobRequest,
     ) -> ClientResult<FineTuningJobObject> {
         let path = format!("/fine_tuning/jobs/{}", req.fine_tuning_job_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<FineTuningJobObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Cancels a fine-tuning job and returns the response.
@@ -452,14 +1094,65 @@ impl Client {
         req: CancelFineTuningJobRequest,
     ) -> ClientResult<FineTuningJobObject> {
         let path = format!("/fine_tuning/jobs/{}/cancel", req.fine_tuning_job_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .send()
-            .await?
-            .json::<FineTuningJobObject>()
+        let response = self.send(self.request(Method::POST, &path, None)).await?;
+        Client::json_response(response).await
+    }
+
+    /// Deletes a fine-tuned model owned by the organization and returns the response.
+    pub async fn delete_model(&self, model: String) -> ClientResult<DeletionStatus> {
+        let path = format!("/models/{}", model);
+        self.delete_json_or_else(&path, || DeletionStatus::deleted(model, "model"))
             .await
-            .map_err(APIError::ReqwestError)
+    }
+
+    /// Creates a batch and returns the response.
+    pub async fn create_batch(
+        &self,
+        req: CreateBatchRequest,
+    ) -> ClientResult<BatchObject> {
+        self.create_batch_with_opts(req, &RequestOptions::default())
+            .await
+    }
+
+    /// Creates a batch with per-request overrides, such as an idempotency key, and returns the
+    /// response.
+    pub async fn create_batch_with_opts(
+        &self,
+        req: CreateBatchRequest,
+        opts: &RequestOptions,
+    ) -> ClientResult<BatchObject> {
+        check_metadata(req.metadata.as_ref())?;
+        self.post_json_with_opts("/batches", &req, opts).await
+    }
+
+    /// Retrieves a batch and returns the response.
+    pub async fn retrieve_batch(&self, batch_id: String) -> ClientResult<BatchObject> {
+        let path = format!("/batches/{}", batch_id);
+        self.get_json(&path).await
+    }
+
+    /// Cancels a batch and returns the response.
+    pub async fn cancel_batch(&self, batch_id: String) -> ClientResult<BatchObject> {
+        let path = format!("/batches/{}/cancel", batch_id);
+        let response = self.send(self.request(Method::POST, &path, None)).await?;
+        Client::json_response(response).await
+    }
+
+    /// Lists batches and returns the response.
+    pub async fn list_batches(
+        &self,
+        limit: Option<i64>,
+        after: Option<String>,
+    ) -> ClientResult<ListBatch> {
+        let path = Client::query_params(
+            limit,
+            None,
+            after,
+            None,
+            Vec::new(),
+            "/batches".to_owned(),
+        );
+        self.get_json(&path).await
     }
 
     /// Creates a moderation request and returns the response.
@@ -467,15 +1160,7 @@ impl Client {
         &self,
         req: CreateModerationRequest,
     ) -> ClientResult<CreateModerationResponse> {
-        let url = Client::from_path("/content-moderation");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<CreateModerationResponse>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json("/moderations", &req).await
     }
 
     /// Creates an assistant and returns the response.
@@ -483,15 +1168,8 @@ impl Client {
         &self,
         req: AssistantRequest,
     ) -> ClientResult<AssistantObject> {
-        let url = Client::from_path("/assistants");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<AssistantObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        check_metadata(req.metadata.as_ref())?;
+        self.post_json("/assistants", &req).await
     }
 
     /// Retrieves an assistant and returns the response.
@@ -500,14 +1178,7 @@ impl Client {
         assistant_id: String,
     ) -> ClientResult<AssistantObject> {
         let path = format!("/assistants/{}", assistant_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<AssistantObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Modifies an assistant and returns the response.
@@ -516,16 +1187,9 @@ impl Client {
         assistant_id: String,
         req: AssistantRequest,
     ) -> ClientResult<AssistantObject> {
+        check_metadata(req.metadata.as_ref())?;
         let path = format!("/assistants/{}", assistant_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<AssistantObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &req).await
     }
 
     /// Deletes an assistant and returns the response.
@@ -534,14 +1198,10 @@ impl Client {
         assistant_id: String,
     ) -> ClientResult<DeletionStatus> {
         let path = format!("/assistants/{}", assistant_id);
-        let url = Client::from_path(&path);
-        self.client
-            .delete(&url)
-            .send()
-            .await?
-            .json::<DeletionStatus>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.delete_json_or_else(&path, || {
+            DeletionStatus::deleted(assistant_id, "assistant.deleted")
+        })
+        .await
     }
 
     /// Lists assistants and returns the response.
@@ -552,15 +1212,15 @@ impl Client {
         after: Option<String>,
         before: Option<String>,
     ) -> ClientResult<ListAssistant> {
-        let base_url = Client::from_path("/assistants");
-        let url = Client::query_params(limit, order, after, before, base_url);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ListAssistant>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let path = Client::query_params(
+            limit,
+            order,
+            after,
+            before,
+            Vec::new(),
+            "/assistants".to_owned(),
+        );
+        self.get_json(&path).await
     }
 
     /// Creates an assistant file and returns the response.
@@ -570,15 +1230,7 @@ impl Client {
         req: AssistantFileRequest,
     ) -> ClientResult<AssistantFileObject> {
         let path = format!("/assistants/{}/files", assistant_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<AssistantFileObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &req).await
     }
 
     /// Retrieves an assistant file and returns the response.
@@ -588,14 +1240,7 @@ impl Client {
         file_id: String,
     ) -> ClientResult<AssistantFileObject> {
         let path = format!("/assistants/{}/files/{}", assistant_id, file_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<AssistantFileObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Deletes an assistant file and returns the response.
@@ -605,14 +1250,10 @@ impl Client {
         file_id: String,
     ) -> ClientResult<DeletionStatus> {
         let path = format!("/assistants/{}/files/{}", assistant_id, file_id);
-        let url = Client::from_path(&path);
-        self.client
-            .delete(&url)
-            .send()
-            .await?
-            .json::<DeletionStatus>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.delete_json_or_else(&path, || {
+            DeletionStatus::deleted(file_id, "assistant.file.deleted")
+        })
+        .await
     }
 
     /// Lists assistant files and returns the response.
@@ -625,15 +1266,8 @@ impl Client {
         before: Option<String>,
     ) -> ClientResult<ListAssistantFile> {
         let path = format!("/assistants/{}/files", assistant_id);
-        let path = Client::query_params(limit, order, after, before, path);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ListAssistantFile>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let path = Client::query_params(limit, order, after, before, Vec::new(), path);
+        self.get_json(&path).await
     }
 
     /// Creates a thread and returns the response.
@@ -641,28 +1275,14 @@ impl Client {
         &self,
         req: CreateThreadRequest,
     ) -> ClientResult<ThreadObject> {
-        let url = Client::from_path("/threads");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<ThreadObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        check_metadata(req.metadata.as_ref())?;
+        self.post_json("/threads", &req).await
     }
 
     /// Retrieves a thread and returns the response.
     pub async fn retrieve_thread(&self, thread_id: String) -> ClientResult<ThreadObject> {
         let path = format!("/threads/{}", thread_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ThreadObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Modifies a thread and returns the response.
@@ -671,29 +1291,18 @@ impl Client {
         thread_id: String,
         req: ModifyThreadRequest,
     ) -> ClientResult<ThreadObject> {
+        check_metadata(req.metadata.as_ref())?;
         let path = format!("/threads/{}", thread_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<ThreadObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &req).await
     }
 
     /// Deletes a thread and returns the response.
     pub async fn delete_thread(&self, thread_id: String) -> ClientResult<DeletionStatus> {
         let path = format!("/threads/{}", thread_id);
-        let url = Client::from_path(&path);
-        self.client
-            .delete(&url)
-            .send()
-            .await?
-            .json::<DeletionStatus>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.delete_json_or_else(&path, || {
+            DeletionStatus::deleted(thread_id, "thread.deleted")
+        })
+        .await
     }
 
     /// Creates a message in a thread and returns the response.
@@ -702,16 +1311,9 @@ impl Client {
         thread_id: String,
         req: CreateMessageRequest,
     ) -> ClientResult<MessageObject> {
+        check_metadata(req.metadata.as_ref())?;
         let path = format!("/threads/{}/messages", thread_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<MessageObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &req).await
     }
 
     /// Retrieves a message in a thread and returns the response.
@@ -721,14 +1323,7 @@ impl Client {
         message_id: String,
     ) -> ClientResult<MessageObject> {
         let path = format!("/threads/{}/messages/{}", thread_id, message_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<MessageObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Modifies a message in a thread and returns the response.
@@ -738,29 +1333,41 @@ impl Client {
         message_id: String,
         req: ModifyMessageRequest,
     ) -> ClientResult<MessageObject> {
+        check_metadata(req.metadata.as_ref())?;
         let path = format!("/threads/{}/messages/{}", thread_id, message_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<MessageObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &req).await
     }
 
-    /// Lists messages in a thread and returns the response.
-    pub async fn list_messages(&self, thread_id: String) -> ClientResult<ListMessage> {
+    /// Lists messages in a thread and returns the response, optionally narrowed to the
+    /// messages created by a single run.
+    pub async fn list_messages(
+        &self,
+        thread_id: String,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+        run_id: Option<String>,
+    ) -> ClientResult<ListMessage> {
         let path = format!("/threads/{}/messages", thread_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ListMessage>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let extra = run_id
+            .map(|run_id| vec![("run_id", run_id)])
+            .unwrap_or_default();
+        let path = Client::query_params(limit, order, after, before, extra, path);
+        self.get_json(&path).await
+    }
+
+    /// Deletes a message from a thread and returns the deletion status.
+    pub async fn delete_message(
+        &self,
+        thread_id: String,
+        message_id: String,
+    ) -> ClientResult<DeletionStatus> {
+        let path = format!("/threads/{}/messages/{}", thread_id, message_id);
+        self.delete_json_or_else(&path, || {
+            DeletionStatus::deleted(message_id, "thread.message.deleted")
+        })
+        .await
     }
 
     /// Retrieves a file associated with a message and returns the response.
@@ -774,14 +1381,7 @@ impl Client {
             "/threads/{}/messages/{}/files/{}",
             thread_id, message_id, file_id
         );
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<MessageFileObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Lists files associated with a message and returns the response.
@@ -795,15 +1395,8 @@ impl Client {
         before: Option<String>,
     ) -> ClientResult<ListMessageFile> {
         let path = format!("/threads/{}/messages/{}/files", thread_id, message_id);
-        let path = Client::query_params(limit, order, after, before, path);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ListMessageFile>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let path = Client::query_params(limit, order, after, before, Vec::new(), path);
+        self.get_json(&path).await
     }
 
     /// Creates a run in a thread and returns the response.
@@ -812,16 +1405,39 @@ impl Client {
         thread_id: String,
         req: CreateRunRequest,
     ) -> ClientResult<RunObject> {
-        let path = format!("/threads/{}/runs", thread_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<RunObject>()
+        self.create_run_with_opts(thread_id, req, &RequestOptions::default())
             .await
-            .map_err(APIError::ReqwestError)
+    }
+
+    /// Creates a run in a thread with per-request overrides, such as an idempotency key, and
+    /// returns the response.
+    pub async fn create_run_with_opts(
+        &self,
+        thread_id: String,
+        req: CreateRunRequest,
+        opts: &RequestOptions,
+    ) -> ClientResult<RunObject> {
+        check_metadata(req.metadata.as_ref())?;
+        let path = format!("/threads/{}/runs", thread_id);
+        self.post_json_with_opts(&path, &req, opts).await
+    }
+
+    /// Creates a run in a thread and returns a stream of the events it emits.
+    pub async fn create_run_stream(
+        &self,
+        thread_id: String,
+        req: CreateRunRequest,
+    ) -> ClientResult<impl Stream<Item = ClientResult<RunStreamEvent>>> {
+        check_metadata(req.metadata.as_ref())?;
+        let path = format!("/threads/{}/runs", thread_id);
+        let req = req.stream(true);
+        Ok(
+            sse_events(self.post_stream(&path, &req).await?).map(|event| {
+                let event = event?;
+                let name = event.event.as_deref().unwrap_or_default();
+                RunStreamEvent::parse(name, &event.data).map_err(APIError::SerdeError)
+            }),
+        )
     }
 
     /// Retrieves a run in a thread and returns the response.
@@ -831,14 +1447,28 @@ impl Client {
         run_id: String,
     ) -> ClientResult<RunObject> {
         let path = format!("/threads/{}/runs/{}", thread_id, run_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<RunObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
+    }
+
+    /// Polls a run until it completes, fails or requires action, and returns it.
+    ///
+    /// Polls start `min_interval` apart and back off up to `max_interval` while the status stays
+    /// the same, going back to `min_interval` whenever it changes.
+    pub async fn wait_for_run(
+        &self,
+        thread_id: String,
+        run_id: String,
+        min_interval: Duration,
+        max_interval: Duration,
+    ) -> ClientResult<RunObject> {
+        let mut backoff = RunPollBackoff::new(min_interval, max_interval);
+        loop {
+            let run = self.retrieve_run(thread_id.clone(), run_id.clone()).await?;
+            if run.is_settled() {
+                return Ok(run);
+            }
+            tokio::time::sleep(backoff.next_delay(&run.status)).await;
+        }
     }
 
     /// Modifies a run in a thread and returns the response.
@@ -848,16 +1478,9 @@ impl Client {
         run_id: String,
         req: ModifyRunRequest,
     ) -> ClientResult<RunObject> {
+        check_metadata(req.metadata.as_ref())?;
         let path = format!("/threads/{}/runs/{}", thread_id, run_id);
-        let url = Client::from_path(&path);
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<RunObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &req).await
     }
 
     /// Lists runs in a thread and returns the response.
@@ -870,15 +1493,8 @@ impl Client {
         before: Option<String>,
     ) -> ClientResult<ListRun> {
         let path = format!("/threads/{}/runs", thread_id);
-        let path = Client::query_params(limit, order, after, before, path);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ListRun>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let path = Client::query_params(limit, order, after, before, Vec::new(), path);
+        self.get_json(&path).await
     }
 
     /// Cancels a run in a thread and returns the response.
@@ -888,16 +1504,8 @@ impl Client {
         run_id: String,
     ) -> ClientResult<RunObject> {
         let path = format!("/threads/{}/runs/{}/cancel", thread_id, run_id);
-        let url = Client::from_path(&path);
         let empty_req = ModifyRunRequest::new();
-        self.client
-            .post(&url)
-            .json(&empty_req)
-            .send()
-            .await?
-            .json::<RunObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.post_json(&path, &empty_req).await
     }
 
     /// Creates a thread and a run and returns the response.
@@ -905,15 +1513,19 @@ impl Client {
         &self,
         req: CreateThreadAndRunRequest,
     ) -> ClientResult<RunObject> {
-        let url = Client::from_path("/threads/runs");
-        self.client
-            .post(&url)
-            .json(&req)
-            .send()
-            .await?
-            .json::<RunObject>()
+        self.create_thread_and_run_with_opts(req, &RequestOptions::default())
             .await
-            .map_err(APIError::ReqwestError)
+    }
+
+    /// Creates a thread and a run with per-request overrides, such as an idempotency key, and
+    /// returns the response.
+    pub async fn create_thread_and_run_with_opts(
+        &self,
+        req: CreateThreadAndRunRequest,
+        opts: &RequestOptions,
+    ) -> ClientResult<RunObject> {
+        check_metadata(req.metadata.as_ref())?;
+        self.post_json_with_opts("/threads/runs", &req, opts).await
     }
 
     /// Retrieves a step in a run and returns the response.
@@ -924,14 +1536,7 @@ impl Client {
         step_id: String,
     ) -> ClientResult<RunStepObject> {
         let path = format!("/threads/{}/runs/{}/steps/{}", thread_id, run_id, step_id);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<RunStepObject>()
-            .await
-            .map_err(APIError::ReqwestError)
+        self.get_json(&path).await
     }
 
     /// Lists steps in a run and returns the response.
@@ -945,41 +1550,291 @@ impl Client {
         before: Option<String>,
     ) -> ClientResult<ListRunStep> {
         let path = format!("/threads/{}/runs/{}/steps", thread_id, run_id);
-        let path = Client::query_params(limit, order, after, before, path);
-        let url = Client::from_path(&path);
-        self.client
-            .get(&url)
-            .send()
-            .await?
-            .json::<ListRunStep>()
-            .await
-            .map_err(APIError::ReqwestError)
+        let path = Client::query_params(limit, order, after, before, Vec::new(), path);
+        self.get_json(&path).await
+    }
+
+    /// Creates a vector store and returns the response.
+    pub async fn create_vector_store(
+        &self,
+        req: CreateVectorStoreRequest,
+    ) -> ClientResult<VectorStoreObject> {
+        check_metadata(req.metadata.as_ref())?;
+        self.post_json("/vector_stores", &req).await
+    }
+
+    /// Retrieves a vector store and returns the response.
+    pub async fn retrieve_vector_store(
+        &self,
+        vector_store_id: String,
+    ) -> ClientResult<VectorStoreObject> {
+        let path = format!("/vector_stores/{}", vector_store_id);
+        self.get_json(&path).await
+    }
+
+    /// Deletes a vector store and returns the response.
+    pub async fn delete_vector_store(
+        &self,
+        vector_store_id: String,
+    ) -> ClientResult<DeletionStatus> {
+        let path = format!("/vector_stores/{}", vector_store_id);
+        self.delete_json_or_else(&path, || {
+            DeletionStatus::deleted(vector_store_id, "vector_store.deleted")
+        })
+        .await
+    }
+
+    /// Lists vector stores and returns the response.
+    pub async fn list_vector_stores(
+        &self,
+        limit: Option<i64>,
+        order: Option<String>,
+        after: Option<String>,
+        before: Option<String>,
+    ) -> ClientResult<ListVectorStore> {
+        let path = Client::query_params(
+            limit,
+            order,
+            after,
+            before,
+            Vec::new(),
+            "/vector_stores".to_owned(),
+        );
+        self.get_json(&path).await
+    }
+
+    /// Adds a file to a vector store and returns the response.
+    pub async fn create_vector_store_file(
+        &self,
+        vector_store_id: String,
+        req: CreateVectorStoreFileRequest,
+    ) -> ClientResult<VectorStoreFileObject> {
+        let path = format!("/vector_stores/{}/files", vector_store_id);
+        self.post_json(&path, &req).await
     }
 
-    /// Constructs a query parameter string from the given options and appends it to the URL.
+    /// Creates a model response and returns it.
+    pub async fn create_response(
+        &self,
+        req: CreateResponseRequest,
+    ) -> ClientResult<ResponseObject> {
+        check_metadata(req.metadata.as_ref())?;
+        self.post_json("/responses", &req).await
+    }
+
+    /// Retrieves a stored model response and returns it.
+    pub async fn retrieve_response(
+        &self,
+        response_id: String,
+    ) -> ClientResult<ResponseObject> {
+        let path = format!("/responses/{}", response_id);
+        self.get_json(&path).await
+    }
+
+    /// Deletes a stored model response and returns the response.
+    pub async fn delete_response(
+        &self,
+        response_id: String,
+    ) -> ClientResult<DeletionStatus> {
+        let path = format!("/responses/{}", response_id);
+        self.delete_json_or_else(&path, || {
+            DeletionStatus::deleted(response_id, "response")
+        })
+        .await
+    }
+
+    /// Constructs a query parameter string from the given options and any `extra` key/value
+    /// pairs, and appends it to the URL. Keys and values are percent-encoded.
     fn query_params(
         limit: Option<i64>,
         order: Option<String>,
         after: Option<String>,
         before: Option<String>,
+        extra: Vec<(&str, String)>,
         mut url: String,
     ) -> String {
-        let mut params = String::new();
+        let mut params = Vec::new();
+        let mut push_param = |key: &str, value: &str| {
+            params.push(format!(
+                "{}={}",
+                utf8_percent_encode(key, QUERY_ENCODE_SET),
+                utf8_percent_encode(value, QUERY_ENCODE_SET)
+            ));
+        };
         if let Some(limit) = limit {
-            params.push_str(&format!("limit={}&", limit));
+            push_param("limit", &limit.to_string());
         }
         if let Some(order) = order {
-            params.push_str(&format!("order={}&", order));
+            push_param("order", &order);
         }
         if let Some(after) = after {
-            params.push_str(&format!("after={}&", after));
+            push_param("after", &after);
         }
         if let Some(before) = before {
-            params.push_str(&format!("before={}&", before));
+            push_param("before", &before);
+        }
+        for (key, value) in extra {
+            push_param(key, &value);
         }
         if !params.is_empty() {
-            url.push_str(&format!("?{params}"));
+            url.push('?');
+            url.push_str(&params.join("&"));
         }
         url
     }
 }
+
+/// Checks an optional request metadata map against the API's limits before it is sent.
+fn check_metadata(metadata: Option<&HashMap<String, String>>) -> ClientResult<()> {
+    metadata.map_or(Ok(()), validate_metadata)
+}
+
+/// Emits a `debug!` event describing an outgoing request, with credentials redacted.
+#[cfg(feature = "tracing")]
+fn log_request(request: &reqwest::Request) {
+    let headers: Vec<String> = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = if name == AUTHORIZATION || name == "api-key" {
+                "[REDACTED]"
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            format!("{}: {}", name, value)
+        })
+        .collect();
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(String::from_utf8_lossy);
+    tracing::debug!(
+        method = %request.method(),
+        url = %request.url(),
+        headers = ?headers,
+        body = body.as_deref().unwrap_or("<stream>"),
+        "sending OpenAI request"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_env_requires_api_key() {
+        match Client::from_env_with(|_| None) {
+            Err(APIError::MissingApiKey(name)) => assert_eq!(name, "OPENAI_API_KEY"),
+            other => panic!("expected a missing key error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn from_env_reads_key_and_base() {
+        let client = Client::from_env_with(|name| match name {
+            "OPENAI_API_KEY" => Some("sk-test".to_owned()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(client.api_key, "sk-test");
+        assert_eq!(client.endpoint, API_URL_V1);
+
+        let client = Client::from_env_with(|name| match name {
+            "OPENAI_API_KEY" => Some("sk-test".to_owned()),
+            "OPENAI_API_BASE" => Some("http://localhost:8080/v1".to_owned()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(client.endpoint, "http://localhost:8080/v1");
+    }
+
+    #[test]
+    fn query_params_appends_extra_params() {
+        let url = Client::query_params(
+            Some(10),
+            None,
+            None,
+            None,
+            vec![("purpose", "fine-tune".to_owned())],
+            "/files".to_owned(),
+        );
+        assert_eq!(url, "/files?limit=10&purpose=fine-tune");
+    }
+
+    #[test]
+    fn query_params_encodes_reserved_characters() {
+        let url = Client::query_params(
+            None,
+            Some("desc".to_owned()),
+            Some("a&b c".to_owned()),
+            None,
+            Vec::new(),
+            "/files".to_owned(),
+        );
+        assert_eq!(url, "/files?order=desc&after=a%26b%20c");
+    }
+
+    #[test]
+    fn query_params_has_no_dangling_separators() {
+        let url = Client::query_params(
+            Some(10),
+            Some("asc".to_owned()),
+            Some("file-abc".to_owned()),
+            Some("file-xyz".to_owned()),
+            Vec::new(),
+            "/files".to_owned(),
+        );
+        assert_eq!(
+            url,
+            "/files?limit=10&order=asc&after=file-abc&before=file-xyz"
+        );
+        let url =
+            Client::query_params(None, None, None, None, Vec::new(), "/files".to_owned());
+        assert_eq!(url, "/files");
+    }
+
+    fn azure_client() -> Client {
+        Client::azure(
+            "https://example.openai.azure.com".to_owned(),
+            "test-key".to_owned(),
+            "2024-06-01".to_owned(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn azure_url_nests_path_under_deployment() {
+        assert_eq!(
+            azure_client().url("/chat/completions", Some("gpt-4o-prod")),
+            "https://example.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions\
+             ?api-version=2024-06-01"
+        );
+        assert_eq!(
+            azure_client().url("/files", None),
+            "https://example.openai.azure.com/openai/files?api-version=2024-06-01"
+        );
+    }
+
+    #[test]
+    fn azure_url_appends_api_version_to_existing_query() {
+        assert_eq!(
+            azure_client().url("/files?purpose=fine-tune", None),
+            "https://example.openai.azure.com/openai/files?purpose=fine-tune\
+             &api-version=2024-06-01"
+        );
+        assert_eq!(
+            azure_client().url("/assistants?limit=10", Some("gpt-4o-prod")),
+            "https://example.openai.azure.com/openai/deployments/gpt-4o-prod/assistants\
+             ?limit=10&api-version=2024-06-01"
+        );
+    }
+
+    #[test]
+    fn openai_url_appends_path_to_endpoint() {
+        let client = Client::new("test-key".to_owned()).unwrap();
+        assert_eq!(
+            client.url("/chat/completions", Some("ignored")),
+            format!("{}/chat/completions", API_URL_V1)
+        );
+    }
+}
@@ -1,12 +1,53 @@
 //! This module defines enums, structs, and macros for handling message roles and usage metrics.
 //! It includes:
 //! - `MessageRole`: Enum representing different roles in a messaging system.
+//! - `FinishReason`: Enum representing why a completion stopped generating.
+//! - `ObjectType`: Enum representing the `object` discriminator of API responses.
 //! - `Usage`: Struct for tracking token usage in prompts and completions.
+//! - `validate_metadata`: Function checking a metadata map against the API's size limits.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
+use crate::error::APIError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use strum::{AsRefStr, Display, EnumString};
 
+/// Maximum number of key-value pairs accepted in a metadata map.
+pub const MAX_METADATA_KEYS: usize = 16;
+/// Maximum length in characters of a metadata key.
+pub const MAX_METADATA_KEY_CHARS: usize = 64;
+/// Maximum length in characters of a metadata value.
+pub const MAX_METADATA_VALUE_CHARS: usize = 512;
+
+/// Checks that `metadata` stays within the API's limits on key count, key length and value
+/// length, which it otherwise rejects with a 400.
+pub fn validate_metadata(metadata: &HashMap<String, String>) -> Result<(), APIError> {
+    if metadata.len() > MAX_METADATA_KEYS {
+        return Err(APIError::Unknown(format!(
+            "metadata has {} keys, above the limit of {}",
+            metadata.len(),
+            MAX_METADATA_KEYS
+        )));
+    }
+    for (key, value) in metadata {
+        let key_chars = key.chars().count();
+        if key_chars > MAX_METADATA_KEY_CHARS {
+            return Err(APIError::Unknown(format!(
+                "metadata key {:?} is {} characters, above the limit of {}",
+                key, key_chars, MAX_METADATA_KEY_CHARS
+            )));
+        }
+        let value_chars = value.chars().count();
+        if value_chars > MAX_METADATA_VALUE_CHARS {
+            return Err(APIError::Unknown(format!(
+                "metadata value for key {:?} is {} characters, above the limit of {}",
+                key, value_chars, MAX_METADATA_VALUE_CHARS
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Represents different roles in a messaging system.
 #[derive(
     Debug, Deserialize, EnumString, Serialize, Clone, PartialEq, Eq, AsRefStr, Display,
@@ -28,10 +69,121 @@ pub enum MessageRole {
     #[serde(rename = "function")]
     #[strum(serialize = "function")]
     Function,
+    /// Represents a tool role, used to return the result of a tool call.
+    #[serde(rename = "tool")]
+    #[strum(serialize = "tool")]
+    Tool,
+    /// Represents a developer role, which replaces the system role on reasoning models.
+    #[serde(rename = "developer")]
+    #[strum(serialize = "developer")]
+    Developer,
 }
 
-/// Struct for tracking token usage.
+/// Reason a chat or text completion stopped generating.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum FinishReason {
+    /// Finished due to reaching stop condition.
+    stop,
+    /// Finished due to reaching maximum length.
+    length,
+    /// Finished due to content filtering.
+    content_filter,
+    /// Finished due to tool calls.
+    tool_calls,
+    /// Null finish reason.
+    null,
+    /// Finish reason not known to this library.
+    #[serde(other)]
+    unknown,
+}
+
+/// Represents the `object` discriminator of API responses, for matching without comparing
+/// strings.
+#[derive(
+    Debug, Deserialize, EnumString, Serialize, Clone, PartialEq, Eq, AsRefStr, Display,
+)]
+pub enum ObjectType {
+    /// A page of objects.
+    #[serde(rename = "list")]
+    #[strum(serialize = "list")]
+    List,
+    /// A chat completion.
+    #[serde(rename = "chat.completion")]
+    #[strum(serialize = "chat.completion")]
+    ChatCompletion,
+    /// A chunk of a streamed chat completion.
+    #[serde(rename = "chat.completion.chunk")]
+    #[strum(serialize = "chat.completion.chunk")]
+    ChatCompletionChunk,
+    /// A text completion.
+    #[serde(rename = "text_completion")]
+    #[strum(serialize = "text_completion")]
+    TextCompletion,
+    /// An embedding.
+    #[serde(rename = "embedding")]
+    #[strum(serialize = "embedding")]
+    Embedding,
+    /// An uploaded file.
+    #[serde(rename = "file")]
+    #[strum(serialize = "file")]
+    File,
+    /// A model.
+    #[serde(rename = "model")]
+    #[strum(serialize = "model")]
+    Model,
+    /// An assistant.
+    #[serde(rename = "assistant")]
+    #[strum(serialize = "assistant")]
+    Assistant,
+    /// A thread.
+    #[serde(rename = "thread")]
+    #[strum(serialize = "thread")]
+    Thread,
+    /// A message in a thread.
+    #[serde(rename = "thread.message")]
+    #[strum(serialize = "thread.message")]
+    ThreadMessage,
+    /// An incremental change to a message.
+    #[serde(rename = "thread.message.delta")]
+    #[strum(serialize = "thread.message.delta")]
+    ThreadMessageDelta,
+    /// A run on a thread.
+    #[serde(rename = "thread.run")]
+    #[strum(serialize = "thread.run")]
+    ThreadRun,
+    /// A step of a run.
+    #[serde(rename = "thread.run.step")]
+    #[strum(serialize = "thread.run.step")]
+    ThreadRunStep,
+    /// An incremental change to a run step.
+    #[serde(rename = "thread.run.step.delta")]
+    #[strum(serialize = "thread.run.step.delta")]
+    ThreadRunStepDelta,
+    /// A batch.
+    #[serde(rename = "batch")]
+    #[strum(serialize = "batch")]
+    Batch,
+    /// A vector store.
+    #[serde(rename = "vector_store")]
+    #[strum(serialize = "vector_store")]
+    VectorStore,
+    /// A fine-tuning job.
+    #[serde(rename = "fine_tuning.job")]
+    #[strum(serialize = "fine_tuning.job")]
+    FineTuningJob,
+    /// A model response.
+    #[serde(rename = "response")]
+    #[strum(serialize = "response")]
+    Response,
+    /// Object type not known to this library.
+    #[serde(other)]
+    #[strum(serialize = "unknown")]
+    Unknown,
+}
+
+/// Struct for tracking token usage.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
 pub struct Usage {
     /// Number of tokens used in the prompt.
     pub prompt_tokens: i32,
@@ -56,3 +208,118 @@ macro_rules! impl_builder_methods {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_and_developer_roles_round_trip() {
+        for (role, wire) in [
+            (MessageRole::Tool, "tool"),
+            (MessageRole::Developer, "developer"),
+        ] {
+            assert_eq!(
+                serde_json::to_string(&role).unwrap(),
+                format!("\"{}\"", wire)
+            );
+            let parsed: MessageRole =
+                serde_json::from_str(&format!("\"{}\"", wire)).unwrap();
+            assert_eq!(parsed, role);
+            assert_eq!(role.to_string(), wire);
+        }
+    }
+
+    #[test]
+    fn object_types_round_trip() {
+        for (object, wire) in [
+            (ObjectType::List, "list"),
+            (ObjectType::ChatCompletion, "chat.completion"),
+            (ObjectType::ChatCompletionChunk, "chat.completion.chunk"),
+            (ObjectType::TextCompletion, "text_completion"),
+            (ObjectType::Embedding, "embedding"),
+            (ObjectType::File, "file"),
+            (ObjectType::Model, "model"),
+            (ObjectType::Assistant, "assistant"),
+            (ObjectType::Thread, "thread"),
+            (ObjectType::ThreadMessage, "thread.message"),
+            (ObjectType::ThreadMessageDelta, "thread.message.delta"),
+            (ObjectType::ThreadRun, "thread.run"),
+            (ObjectType::ThreadRunStep, "thread.run.step"),
+            (ObjectType::ThreadRunStepDelta, "thread.run.step.delta"),
+            (ObjectType::Batch, "batch"),
+            (ObjectType::VectorStore, "vector_store"),
+            (ObjectType::FineTuningJob, "fine_tuning.job"),
+            (ObjectType::Response, "response"),
+        ] {
+            let json = serde_json::to_string(&object).unwrap();
+            assert_eq!(json, format!("\"{}\"", wire));
+            assert_eq!(serde_json::from_str::<ObjectType>(&json).unwrap(), object);
+            assert_eq!(object.to_string(), wire);
+        }
+    }
+
+    #[test]
+    fn unrecognized_object_type_deserializes_as_unknown() {
+        let object: ObjectType =
+            serde_json::from_str("\"organization.project\"").unwrap();
+        assert_eq!(object, ObjectType::Unknown);
+    }
+
+    #[test]
+    fn validate_metadata_accepts_limits() {
+        let mut metadata: HashMap<String, String> = (0..MAX_METADATA_KEYS)
+            .map(|i| (format!("key{}", i), String::new()))
+            .collect();
+        metadata.insert(
+            "k".repeat(MAX_METADATA_KEY_CHARS),
+            "v".repeat(MAX_METADATA_VALUE_CHARS),
+        );
+        metadata.remove("key0");
+        assert!(validate_metadata(&metadata).is_ok());
+    }
+
+    #[test]
+    fn validate_metadata_rejects_too_many_keys() {
+        let metadata: HashMap<String, String> = (0..=MAX_METADATA_KEYS)
+            .map(|i| (format!("key{}", i), String::new()))
+            .collect();
+        match validate_metadata(&metadata) {
+            Err(APIError::Unknown(err)) => {
+                assert_eq!(err, "metadata has 17 keys, above the limit of 16")
+            }
+            other => panic!("expected a key count error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_metadata_rejects_long_key() {
+        let metadata =
+            HashMap::from([("k".repeat(MAX_METADATA_KEY_CHARS + 1), String::new())]);
+        match validate_metadata(&metadata) {
+            Err(APIError::Unknown(err)) => {
+                assert!(
+                    err.contains("is 65 characters, above the limit of 64"),
+                    "{}",
+                    err
+                )
+            }
+            other => panic!("expected a key length error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_metadata_rejects_long_value() {
+        let metadata = HashMap::from([(
+            "user_id".to_owned(),
+            "v".repeat(MAX_METADATA_VALUE_CHARS + 1),
+        )]);
+        match validate_metadata(&metadata) {
+            Err(APIError::Unknown(err)) => assert_eq!(
+                err,
+                "metadata value for key \"user_id\" is 513 characters, above the limit of 512"
+            ),
+            other => panic!("expected a value length error, got {:?}", other),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! This module defines the structures and methods for handling text completion requests and responses.
 //! It includes:
 //! - `CompletionRequest`: Struct for creating a request to generate text completions.
+//! - `CompletionPrompt`: Enum for a prompt given as text, a token array, or a batch of texts.
 //! - `CompletionChoice`: Struct representing a single completion choice from the response.
 //! - `LogprobResult`: Struct for log probability results associated with completions.
 //! - `CompletionResponse`: Struct for the response from a completion request.
@@ -10,7 +11,50 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::option::Option;
 
-use crate::{common, impl_builder_methods, models::Model};
+use crate::{
+    common, impl_builder_methods,
+    models::{Model, GPT3},
+};
+
+/// Represents the prompt of a completion request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CompletionPrompt {
+    /// A single text prompt.
+    Text(String),
+    /// A single pre-tokenized prompt.
+    Tokens(Vec<i32>),
+    /// A batch of text prompts, each producing its own completions.
+    Batch(Vec<String>),
+}
+
+impl From<String> for CompletionPrompt {
+    /// Converts a string into `CompletionPrompt::Text`.
+    fn from(text: String) -> Self {
+        CompletionPrompt::Text(text)
+    }
+}
+
+impl From<&str> for CompletionPrompt {
+    /// Converts a string into `CompletionPrompt::Text`.
+    fn from(text: &str) -> Self {
+        CompletionPrompt::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<i32>> for CompletionPrompt {
+    /// Converts a token array into `CompletionPrompt::Tokens`.
+    fn from(tokens: Vec<i32>) -> Self {
+        CompletionPrompt::Tokens(tokens)
+    }
+}
+
+impl From<Vec<String>> for CompletionPrompt {
+    /// Converts a list of strings into `CompletionPrompt::Batch`.
+    fn from(texts: Vec<String>) -> Self {
+        CompletionPrompt::Batch(texts)
+    }
+}
 
 /// Represents a request to generate text completions.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -18,7 +62,7 @@ pub struct CompletionRequest {
     /// Model to be used for generating completions.
     pub model: Model,
     /// Prompt for generating the completions.
-    pub prompt: String,
+    pub prompt: CompletionPrompt,
     /// Optional suffix that comes after the generated text.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub suffix: Option<String>,
@@ -65,10 +109,10 @@ pub struct CompletionRequest {
 
 impl CompletionRequest {
     /// Creates a new `CompletionRequest` with the specified model and prompt.
-    pub fn new(model: Model, prompt: String) -> Self {
+    pub fn new(model: Model, prompt: impl Into<CompletionPrompt>) -> Self {
         Self {
             model,
-            prompt,
+            prompt: prompt.into(),
             suffix: None,
             max_tokens: None,
             temperature: None,
@@ -87,6 +131,20 @@ impl CompletionRequest {
     }
 }
 
+impl From<&str> for CompletionRequest {
+    /// Converts a string into a `CompletionRequest` for `gpt-3.5-turbo-instruct`.
+    fn from(prompt: &str) -> Self {
+        CompletionRequest::new(Model::GPT3(GPT3::GPT35TurboInstruct), prompt)
+    }
+}
+
+impl From<String> for CompletionRequest {
+    /// Converts a string into a `CompletionRequest` for `gpt-3.5-turbo-instruct`.
+    fn from(prompt: String) -> Self {
+        CompletionRequest::new(Model::GPT3(GPT3::GPT35TurboInstruct), prompt)
+    }
+}
+
 impl_builder_methods!(
     CompletionRequest,
     suffix: String,
@@ -112,8 +170,8 @@ pub struct CompletionChoice {
     pub text: String,
     /// Index of the completion choice.
     pub index: i64,
-    /// Reason why the completion finished.
-    pub finish_reason: String,
+    /// Reason why the completion finished, absent on intermediate streamed chunks.
+    pub finish_reason: Option<common::FinishReason>,
     /// Optional log probability results for the tokens.
     pub logprobs: Option<LogprobResult>,
 }
@@ -144,8 +202,71 @@ pub struct CompletionResponse {
     pub model: String,
     /// List of completion choices.
     pub choices: Vec<CompletionChoice>,
-    /// Usage information for the completion request.
+    /// Usage information for the completion request, zeroed on streamed chunks.
+    #[serde(default)]
     pub usage: common::Usage,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::FinishReason;
+    use serde_json::json;
+
+    #[test]
+    fn prompt_variants_serialize_untagged() {
+        for (prompt, wire) in [
+            (CompletionPrompt::from("Say hi"), json!("Say hi")),
+            (CompletionPrompt::from(vec![1, 2, 3]), json!([1, 2, 3])),
+            (
+                CompletionPrompt::from(vec!["Say hi".to_owned(), "Say bye".to_owned()]),
+                json!(["Say hi", "Say bye"]),
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(&prompt).unwrap(), wire);
+            assert_eq!(
+                serde_json::from_value::<CompletionPrompt>(wire).unwrap(),
+                prompt
+            );
+        }
+    }
+
+    fn completion_choice(finish_reason: serde_json::Value) -> CompletionChoice {
+        serde_json::from_value(json!({
+            "text": "Hi",
+            "index": 0,
+            "finish_reason": finish_reason,
+            "logprobs": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn finish_reason_deserializes_into_enum() {
+        for (wire, reason) in [
+            (json!("stop"), Some(FinishReason::stop)),
+            (json!("length"), Some(FinishReason::length)),
+            (json!("content_filter"), Some(FinishReason::content_filter)),
+            (json!("some_future_reason"), Some(FinishReason::unknown)),
+            (json!(null), None),
+        ] {
+            assert_eq!(completion_choice(wire).finish_reason, reason);
+        }
+    }
+
+    #[test]
+    fn converts_str_into_instruct_request() {
+        let req = CompletionRequest::from("Say this is a test");
+        assert_eq!(req.model.to_string(), "gpt-3.5-turbo-instruct");
+        assert_eq!(
+            req.prompt,
+            CompletionPrompt::Text("Say this is a test".to_owned())
+        );
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({ "model": "gpt-3.5-turbo-instruct", "prompt": "Say this is a test" })
+        );
+    }
+}
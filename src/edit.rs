@@ -3,14 +3,20 @@
 //! - `EditRequest`: Struct for creating a request to edit text.
 //! - `EditChoice`: Struct representing a single edit choice from the response.
 //! - `EditResponse`: Struct for the response from an edit request.
+//! - `EditRequest::to_chat_request` / `EditResponse::from_chat_response`: Conversions that run
+//!   an edit as a chat completion, since the `/edits` endpoint is deprecated.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::option::Option;
 
-use crate::common;
+use crate::chat_completion::{
+    ChatCompletionMessage, ChatCompletionRequest, ChatCompletionResponse, Content,
+};
+use crate::common::{self, MessageRole};
 use crate::impl_builder_methods;
+use crate::models::Model;
 
 /// Represents a request to edit text.
 #[derive(Debug, Serialize, Clone)]
@@ -45,6 +51,30 @@ impl EditRequest {
             top_p: None,
         }
     }
+
+    /// Builds the chat completion request equivalent to this edit, sending the instruction as
+    /// the system message and the input as the user message.
+    pub fn to_chat_request(&self) -> ChatCompletionRequest {
+        let message = |role, text: &str| ChatCompletionMessage {
+            role,
+            content: Content::Text(text.to_owned()),
+            name: None,
+            tool_call_id: None,
+            tool_calls: None,
+        };
+        let mut req = ChatCompletionRequest::new_multi(
+            Model::default(),
+            vec![
+                message(MessageRole::System, &self.instruction),
+                message(MessageRole::User, self.input.as_deref().unwrap_or_default()),
+            ],
+        );
+        req.model = self.model.clone();
+        req.n = self.n.map(i64::from);
+        req.temperature = self.temperature.map(f64::from);
+        req.top_p = self.top_p.map(f64::from);
+        req
+    }
 }
 
 impl_builder_methods!(
@@ -78,3 +108,24 @@ pub struct EditResponse {
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
+
+impl EditResponse {
+    /// Maps a chat completion response produced by `EditRequest::to_chat_request` back into an
+    /// `EditResponse`, taking each choice's message content as the edited text.
+    pub fn from_chat_response(response: ChatCompletionResponse) -> Self {
+        Self {
+            object: "edit".to_owned(),
+            created: response.created,
+            usage: response.usage,
+            choices: response
+                .choices
+                .into_iter()
+                .map(|choice| EditChoice {
+                    text: choice.message.content.unwrap_or_default(),
+                    index: choice.index as i32,
+                })
+                .collect(),
+            headers: response.headers,
+        }
+    }
+}
@@ -1,12 +1,17 @@
 //! This module defines the structures and methods for handling text embeddings.
 //! It includes:
 //! - `EmbeddingData`: Struct representing the data of an embedding.
+//! - `EmbeddingInput`: Enum for an input given as a single text or a batch of texts.
 //! - `EmbeddingRequest`: Struct for creating a request to generate embeddings.
 //! - `EmbeddingResponse`: Struct for the response from an embedding request.
+//! - `EmbeddingResponse::into_ordered` / `zip_with`: Methods mapping embeddings back to their inputs.
+//! - `EmbeddingResponse::first_vector`: Method returning the embedding of a single-text input.
+//! - `EmbeddingData::cosine_similarity` / `top_k_similar`: Helpers ranking embeddings by similarity.
+//! - `EmbeddingRequest::validate`: Method checking that `dimensions` is only sent to models supporting it.
 //! - `Usage`: Struct for tracking token usage in embedding operations.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
-use crate::{impl_builder_methods, models::Model};
+use crate::{error::APIError, impl_builder_methods, models::Model};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, option::Option};
 
@@ -21,13 +26,84 @@ pub struct EmbeddingData {
     pub index: i32,
 }
 
+impl EmbeddingData {
+    /// Returns the cosine similarity between this embedding and `other`, from -1 to 1.
+    pub fn cosine_similarity(&self, other: &[f32]) -> f32 {
+        cosine_similarity(&self.embedding, other)
+    }
+}
+
+/// Returns the `k` entries of `corpus` most similar to `query`, most similar first, along with
+/// their cosine similarity.
+pub fn top_k_similar<'a>(
+    query: &[f32],
+    corpus: &'a [(&'a str, Vec<f32>)],
+    k: usize,
+) -> Vec<(&'a str, f32)> {
+    let mut scored: Vec<(&str, f32)> = corpus
+        .iter()
+        .map(|(text, embedding)| (*text, cosine_similarity(query, embedding)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(k);
+    scored
+}
+
+/// Returns the cosine similarity of two vectors, or 0 if either has no magnitude.
+///
+/// Vectors of different lengths are compared over their common prefix.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (dot, norm_a, norm_b) = a
+        .iter()
+        .zip(b)
+        .fold((0.0, 0.0, 0.0), |(dot, norm_a, norm_b), (x, y)| {
+            (dot + x * y, norm_a + x * x, norm_b + y * y)
+        });
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+/// Represents the input of an embedding request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum EmbeddingInput {
+    /// A single text.
+    Text(String),
+    /// A batch of texts, each producing its own embedding.
+    Batch(Vec<String>),
+}
+
+impl From<String> for EmbeddingInput {
+    /// Converts a string into `EmbeddingInput::Text`.
+    fn from(text: String) -> Self {
+        EmbeddingInput::Text(text)
+    }
+}
+
+impl From<&str> for EmbeddingInput {
+    /// Converts a string into `EmbeddingInput::Text`.
+    fn from(text: &str) -> Self {
+        EmbeddingInput::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<String>> for EmbeddingInput {
+    /// Converts a list of strings into `EmbeddingInput::Batch`.
+    fn from(texts: Vec<String>) -> Self {
+        EmbeddingInput::Batch(texts)
+    }
+}
+
 /// Represents a request to generate embeddings.
 #[derive(Debug, Serialize, Clone)]
 pub struct EmbeddingRequest {
     /// Model to be used for generating embeddings.
     pub model: Model,
-    /// Input text for which embeddings are to be generated.
-    pub input: String,
+    /// Input text, or batch of texts, for which embeddings are to be generated.
+    pub input: EmbeddingInput,
     /// Optional dimensions of the embedding.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dimensions: Option<i32>,
@@ -37,19 +113,38 @@ pub struct EmbeddingRequest {
 }
 
 impl EmbeddingRequest {
-    /// Creates a new `EmbeddingRequest` with the specified model and input text.
-    pub fn new(model: Model, input: String) -> Self {
+    /// Creates a new `EmbeddingRequest` with the specified model and input text or texts.
+    pub fn new(model: Model, input: impl Into<EmbeddingInput>) -> Self {
         Self {
             model,
-            input,
+            input: input.into(),
             dimensions: None,
             user: None,
         }
     }
+
+    /// Checks that `dimensions` is only set for models that support it.
+    ///
+    /// `Model::Custom` names, such as Azure deployments or models on compatible servers, are
+    /// not checked, as their capabilities are unknown.
+    pub fn validate(&self) -> Result<(), APIError> {
+        if self.dimensions.is_none() {
+            return Ok(());
+        }
+        match &self.model {
+            Model::Embedding(model) if model.supports_dimensions() => Ok(()),
+            Model::Custom(_) => Ok(()),
+            model => Err(APIError::Unknown(format!(
+                "dimensions is not supported by model {}",
+                model.to_string()
+            ))),
+        }
+    }
 }
 
 impl_builder_methods!(
     EmbeddingRequest,
+    dimensions: i32,
     user: String
 );
 
@@ -68,6 +163,36 @@ pub struct EmbeddingResponse {
     pub headers: Option<HashMap<String, String>>,
 }
 
+impl EmbeddingResponse {
+    /// Returns the embedding of the first input, the only one for `EmbeddingInput::Text`, or
+    /// `None` if the response has no embeddings.
+    pub fn first_vector(&self) -> Option<&[f32]> {
+        self.data
+            .iter()
+            .min_by_key(|data| data.index)
+            .map(|data| data.embedding.as_slice())
+    }
+
+    /// Returns the embedding vectors sorted by `index`, matching the order of the inputs.
+    pub fn into_ordered(mut self) -> Vec<Vec<f32>> {
+        self.data.sort_by_key(|data| data.index);
+        self.data.into_iter().map(|data| data.embedding).collect()
+    }
+
+    /// Pairs each input with its embedding by `index`, skipping embeddings whose index has no
+    /// matching input.
+    pub fn zip_with<'a>(&self, inputs: &'a [String]) -> Vec<(&'a str, &[f32])> {
+        let mut data: Vec<&EmbeddingData> = self.data.iter().collect();
+        data.sort_by_key(|data| data.index);
+        data.into_iter()
+            .filter_map(|data| {
+                let input = inputs.get(usize::try_from(data.index).ok()?)?;
+                Some((input.as_str(), data.embedding.as_slice()))
+            })
+            .collect()
+    }
+}
+
 /// Represents token usage in embedding operations.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Usage {
@@ -76,3 +201,123 @@ pub struct Usage {
     /// Total number of tokens used.
     pub total_tokens: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EmbeddingsModels;
+
+    #[test]
+    fn validate_accepts_dimensions_for_v3_models() {
+        let req = EmbeddingRequest::new(
+            Model::Embedding(EmbeddingsModels::TextEmbedding3Small),
+            "text",
+        )
+        .dimensions(256);
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_dimensions_for_legacy_models() {
+        let req = EmbeddingRequest::new(
+            Model::Embedding(EmbeddingsModels::TextEmbeddingAda002),
+            "text",
+        )
+        .dimensions(256);
+        assert!(req.validate().is_err());
+        let req = EmbeddingRequest::new(
+            Model::Embedding(EmbeddingsModels::TextEmbeddingAda002),
+            "text",
+        );
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_lets_custom_models_set_dimensions() {
+        let req = EmbeddingRequest::new(Model::custom("my-embedding-deployment"), "text")
+            .dimensions(256);
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn maps_out_of_order_embeddings_back_to_inputs() {
+        let response: EmbeddingResponse = serde_json::from_str(
+            r#"{
+                "object": "list",
+                "data": [
+                    {"object": "embedding", "embedding": [2.0], "index": 2},
+                    {"object": "embedding", "embedding": [0.0], "index": 0},
+                    {"object": "embedding", "embedding": [1.0], "index": 1}
+                ],
+                "model": "text-embedding-3-small",
+                "usage": {"prompt_tokens": 3, "total_tokens": 3}
+            }"#,
+        )
+        .unwrap();
+        let inputs = vec!["zero".to_owned(), "one".to_owned()];
+
+        assert_eq!(response.first_vector(), Some(&[0.0][..]));
+        assert_eq!(
+            response.zip_with(&inputs),
+            vec![("zero", &[0.0][..]), ("one", &[1.0][..])]
+        );
+        assert_eq!(
+            response.into_ordered(),
+            vec![vec![0.0], vec![1.0], vec![2.0]]
+        );
+    }
+
+    #[test]
+    fn cosine_similarity_of_hand_computed_vectors() {
+        let data = EmbeddingData {
+            object: "embedding".to_owned(),
+            embedding: vec![1.0, 0.0],
+            index: 0,
+        };
+        assert_eq!(data.cosine_similarity(&[2.0, 0.0]), 1.0);
+        assert_eq!(data.cosine_similarity(&[0.0, 3.0]), 0.0);
+        assert_eq!(data.cosine_similarity(&[-1.0, 0.0]), -1.0);
+        assert!((data.cosine_similarity(&[1.0, 1.0]) - 0.5f32.sqrt()).abs() < 1e-6);
+        assert_eq!(data.cosine_similarity(&[0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn top_k_similar_ranks_most_similar_first() {
+        let corpus = [
+            ("east", vec![1.0, 0.0]),
+            ("north", vec![0.0, 1.0]),
+            ("west", vec![-1.0, 0.0]),
+            ("north-east", vec![1.0, 1.0]),
+        ];
+        let top = top_k_similar(&[1.0, 0.0], &corpus, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0], ("east", 1.0));
+        assert_eq!(top[1].0, "north-east");
+        assert!((top[1].1 - 0.5f32.sqrt()).abs() < 1e-6);
+        assert_eq!(top_k_similar(&[1.0, 0.0], &corpus, 10).len(), 4);
+    }
+
+    #[test]
+    fn first_vector_handles_empty_and_populated_responses() {
+        let response = |data: &str| -> EmbeddingResponse {
+            serde_json::from_str(&format!(
+                r#"{{
+                    "object": "list",
+                    "data": {},
+                    "model": "text-embedding-3-small",
+                    "usage": {{"prompt_tokens": 1, "total_tokens": 1}}
+                }}"#,
+                data
+            ))
+            .unwrap()
+        };
+        assert_eq!(response("[]").first_vector(), None);
+        assert_eq!(
+            response(
+                r#"[{"object": "embedding", "embedding": [0.25, -0.5], "index": 0}]"#
+            )
+            .first_vector(),
+            Some(&[0.25, -0.5][..])
+        );
+    }
+}
@@ -2,6 +2,8 @@
 //! The `APIError` enum provides variants for different kinds of errors that may occur
 //! when interacting with APIs, including network errors, serialization errors, and more.
 
+use serde::Deserialize;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Enum representing different kinds of API-related errors.
@@ -26,4 +28,131 @@ pub enum APIError {
     /// Error variant for invalid header values, originating from the `reqwest` library.
     #[error("HeaderError: {0}")]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+
+    /// Error variant for a missing API key when building a client from the environment.
+    #[error("MissingApiKey: {0} is not set")]
+    MissingApiKey(String),
+
+    /// Error variant for a response, or a chunk of a streamed response, that did not arrive
+    /// within the configured read timeout.
+    #[error("Timeout: no data received within {0:?}")]
+    Timeout(Duration),
+
+    /// Error variant for a request abandoned because its cancellation token was triggered.
+    #[error("Cancelled: the request was cancelled")]
+    Cancelled,
+
+    /// Error variant for non-success HTTP responses returned by the API.
+    #[error("ApiError ({status}): {message}")]
+    ApiError {
+        /// HTTP status code of the response.
+        status: u16,
+        /// Error message returned by the API, or the raw body if it could not be parsed.
+        message: String,
+        /// Optional error type, such as `invalid_request_error`.
+        error_type: Option<String>,
+        /// Optional machine-readable error code.
+        code: Option<String>,
+        /// Optional name of the parameter that caused the error.
+        param: Option<String>,
+    },
+}
+
+/// Envelope of the JSON error body returned by the API.
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+/// Details of an error returned by the API.
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    message: String,
+    #[serde(rename = "type")]
+    error_type: Option<String>,
+    code: Option<String>,
+    param: Option<String>,
+}
+
+impl APIError {
+    /// Builds an `APIError::ApiError` from a status code and a raw response body.
+    pub fn from_error_body(status: u16, body: &str) -> Self {
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(ErrorBody { error }) => APIError::ApiError {
+                status,
+                message: error.message,
+                error_type: error.error_type,
+                code: error.code,
+                param: error.param,
+            },
+            Err(_) => APIError::ApiError {
+                status,
+                message: body.to_owned(),
+                error_type: None,
+                code: None,
+                param: None,
+            },
+        }
+    }
+
+    /// Builds an `APIError::ApiError` from an error response, keeping the raw body as the
+    /// message when it does not follow the OpenAI error envelope.
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        match response.text().await {
+            Ok(body) => APIError::from_error_body(status, &body),
+            Err(err) => APIError::ReqwestError(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_error_body_keeps_plain_text_body() {
+        match APIError::from_error_body(502, "Bad Gateway") {
+            APIError::ApiError {
+                status,
+                message,
+                error_type,
+                code,
+                param,
+            } => {
+                assert_eq!(status, 502);
+                assert_eq!(message, "Bad Gateway");
+                assert!(error_type.is_none() && code.is_none() && param.is_none());
+            }
+            other => panic!("expected an API error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn from_error_body_parses_error_envelope() {
+        let body = r#"{
+            "error": {
+                "message": "Invalid value for 'temperature'.",
+                "type": "invalid_request_error",
+                "param": "temperature",
+                "code": "invalid_value"
+            }
+        }"#;
+        match APIError::from_error_body(400, body) {
+            APIError::ApiError {
+                status,
+                message,
+                error_type,
+                code,
+                param,
+            } => {
+                assert_eq!(status, 400);
+                assert_eq!(message, "Invalid value for 'temperature'.");
+                assert_eq!(error_type.as_deref(), Some("invalid_request_error"));
+                assert_eq!(code.as_deref(), Some("invalid_value"));
+                assert_eq!(param.as_deref(), Some("temperature"));
+            }
+            other => panic!("expected an API error, got {:?}", other),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 //! This module defines the structures and methods for managing files, including uploading, deleting, retrieving, and listing files.
 //! It includes:
+//! - `FilePurpose`: Enum representing the intended purpose of a file.
 //! - `FileData`: Struct representing the data of a file.
 //! - `FileListResponse`: Struct for the response from a request to list files.
 //! - `FileUploadRequest`: Struct for creating a request to upload a file.
+//! - `upload_form`: Function building the upload form for in-memory data.
 //! - `FileUploadResponse`: Struct for the response from a file upload request.
 //! - `FileDeleteRequest`: Struct for creating a request to delete a file.
 //! - `FileDeleteResponse`: Struct for the response from a file delete request.
@@ -10,9 +12,110 @@
 //! - `FileRetrieveResponse`: Struct for the response from a file retrieve request.
 //! - `FileRetrieveContentRequest`: Struct for creating a request to retrieve the content of a file.
 //! - `FileRetrieveContentResponse`: Struct for the response from a file content retrieve request.
+//! - `check_file_size`: Function checking a file against an upload limit before it is sent.
 
+use crate::error::APIError;
+use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
+use strum::{AsRefStr, Display};
+
+/// Maximum size in bytes of a file accepted by the file upload endpoint.
+pub const MAX_FILE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Checks that the file at `path` is no larger than `limit` bytes, so oversized uploads fail
+/// fast with an error stating the actual size instead of an opaque server error.
+pub async fn check_file_size(path: &str, limit: u64) -> Result<(), APIError> {
+    let size = async_std::fs::metadata(path).await?.len();
+    check_size(path, size, limit)
+}
+
+/// Returns an error naming `name` and its `size` if it is larger than `limit` bytes.
+fn check_size(name: &str, size: u64, limit: u64) -> Result<(), APIError> {
+    if size > limit {
+        return Err(APIError::Unknown(format!(
+            "file {} is {} bytes, exceeding the {} byte upload limit",
+            name, size, limit
+        )));
+    }
+    Ok(())
+}
+
+/// Represents the intended purpose of an uploaded file.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, AsRefStr, Display)]
+pub enum FilePurpose {
+    /// File used as training or validation data for fine-tuning.
+    #[serde(rename = "fine-tune")]
+    #[strum(serialize = "fine-tune")]
+    FineTune,
+    /// File used by assistants and their tools.
+    #[serde(rename = "assistants")]
+    #[strum(serialize = "assistants")]
+    Assistants,
+    /// File used as input for the batch API.
+    #[serde(rename = "batch")]
+    #[strum(serialize = "batch")]
+    Batch,
+    /// Image file used for vision fine-tuning.
+    #[serde(rename = "vision")]
+    #[strum(serialize = "vision")]
+    Vision,
+    /// File used as input to models, such as a PDF given to a chat completion.
+    #[serde(rename = "user_data")]
+    #[strum(serialize = "user_data")]
+    UserData,
+    /// File used as an eval data source.
+    #[serde(rename = "evals")]
+    #[strum(serialize = "evals")]
+    Evals,
+    /// Results file produced by a fine-tuning job.
+    #[serde(rename = "fine-tune-results")]
+    #[strum(serialize = "fine-tune-results")]
+    FineTuneResults,
+    /// File produced by an assistant's tools.
+    #[serde(rename = "assistants_output")]
+    #[strum(serialize = "assistants_output")]
+    AssistantsOutput,
+    /// Output or error file produced by a batch.
+    #[serde(rename = "batch_output")]
+    #[strum(serialize = "batch_output")]
+    BatchOutput,
+    /// Any purpose not known to this library; it can be read but never sent.
+    #[serde(other)]
+    #[strum(serialize = "other")]
+    Other,
+}
+
+impl From<&str> for FilePurpose {
+    /// Converts a wire string into a `FilePurpose`, falling back to `FilePurpose::Other`.
+    fn from(purpose: &str) -> Self {
+        match purpose {
+            "fine-tune" => FilePurpose::FineTune,
+            "assistants" => FilePurpose::Assistants,
+            "batch" => FilePurpose::Batch,
+            "vision" => FilePurpose::Vision,
+            "user_data" => FilePurpose::UserData,
+            "evals" => FilePurpose::Evals,
+            "fine-tune-results" => FilePurpose::FineTuneResults,
+            "assistants_output" => FilePurpose::AssistantsOutput,
+            "batch_output" => FilePurpose::BatchOutput,
+            _ => FilePurpose::Other,
+        }
+    }
+}
+
+impl FilePurpose {
+    /// Checks that the purpose can be sent to the API, rejecting `FilePurpose::Other`, which
+    /// would otherwise be sent as the invalid purpose `"other"`.
+    pub fn validate(&self) -> Result<(), APIError> {
+        match self {
+            FilePurpose::Other => Err(APIError::Unknown(
+                "file purpose is not known to this library and cannot be sent".to_owned(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
 
 /// Represents the data of a file.
 #[derive(Debug, Deserialize, Serialize)]
@@ -28,7 +131,7 @@ pub struct FileData {
     /// Name of the file.
     pub filename: String,
     /// Purpose of the file.
-    pub purpose: String,
+    pub purpose: FilePurpose,
 }
 
 /// Represents the response from a request to list files.
@@ -38,6 +141,15 @@ pub struct FileListResponse {
     pub object: String,
     /// List of file data.
     pub data: Vec<FileData>,
+    /// Optional ID of the first file in the page.
+    #[serde(default)]
+    pub first_id: Option<String>,
+    /// Optional ID of the last file in the page.
+    #[serde(default)]
+    pub last_id: Option<String>,
+    /// Indicates if there are more files available.
+    #[serde(default)]
+    pub has_more: bool,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
@@ -48,14 +160,49 @@ pub struct FileUploadRequest {
     /// Path to the file to be uploaded.
     pub file: String,
     /// Purpose of the file.
-    pub purpose: String,
+    pub purpose: FilePurpose,
 }
 
 impl FileUploadRequest {
     /// Creates a new `FileUploadRequest` with the specified file and purpose.
-    pub fn new(file: String, purpose: String) -> Self {
+    pub fn new(file: String, purpose: FilePurpose) -> Self {
         Self { file, purpose }
     }
+
+    /// Builds the multipart form for this request, reading the file from disk.
+    ///
+    /// Returns an error without reading the file if it exceeds `MAX_FILE_BYTES` or its purpose is
+    /// `FilePurpose::Other`.
+    pub async fn form(&self) -> Result<Form, APIError> {
+        self.purpose.validate()?;
+        check_file_size(&self.file, MAX_FILE_BYTES).await?;
+        let bytes = async_std::fs::read(&self.file).await?;
+        let filename = Path::new(&self.file)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.file.clone());
+        Ok(bytes_form(bytes, filename, &self.purpose))
+    }
+}
+
+/// Builds the multipart form uploading in-memory `bytes` as a file named `filename`.
+///
+/// Returns an error if the data exceeds `MAX_FILE_BYTES` or the purpose is `FilePurpose::Other`.
+pub fn upload_form(
+    bytes: Vec<u8>,
+    filename: String,
+    purpose: &FilePurpose,
+) -> Result<Form, APIError> {
+    purpose.validate()?;
+    check_size(&filename, bytes.len() as u64, MAX_FILE_BYTES)?;
+    Ok(bytes_form(bytes, filename, purpose))
+}
+
+/// Builds the multipart form carrying the purpose and the file data.
+fn bytes_form(bytes: Vec<u8>, filename: String, purpose: &FilePurpose) -> Form {
+    Form::new()
+        .text("purpose", purpose.to_string())
+        .part("file", Part::bytes(bytes).file_name(filename))
 }
 
 /// Represents the response from a file upload request.
@@ -72,7 +219,7 @@ pub struct FileUploadResponse {
     /// Name of the file.
     pub filename: String,
     /// Purpose of the file.
-    pub purpose: String,
+    pub purpose: FilePurpose,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
@@ -99,11 +246,23 @@ pub struct FileDeleteResponse {
     /// Object type, typically "file".
     pub object: String,
     /// Indicates whether the file was successfully deleted.
-    pub delete: bool,
+    pub deleted: bool,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
 
+impl FileDeleteResponse {
+    /// Creates a successful `FileDeleteResponse` for the given file ID.
+    pub fn deleted(id: String) -> Self {
+        Self {
+            id,
+            object: "file".to_owned(),
+            deleted: true,
+            headers: None,
+        }
+    }
+}
+
 /// Represents a request to retrieve a file.
 #[derive(Debug, Serialize)]
 pub struct FileRetrieveRequest {
@@ -132,7 +291,7 @@ pub struct FileRetrieveResponse {
     /// Name of the file.
     pub filename: String,
     /// Purpose of the file.
-    pub purpose: String,
+    pub purpose: FilePurpose,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
@@ -165,7 +324,75 @@ pub struct FileRetrieveContentResponse {
     /// Name of the file.
     pub filename: String,
     /// Purpose of the file.
-    pub purpose: String,
+    pub purpose: FilePurpose,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purposes_round_trip_through_wire_strings() {
+        for (purpose, wire) in [
+            (FilePurpose::FineTune, "fine-tune"),
+            (FilePurpose::Assistants, "assistants"),
+            (FilePurpose::Batch, "batch"),
+            (FilePurpose::Vision, "vision"),
+            (FilePurpose::UserData, "user_data"),
+            (FilePurpose::Evals, "evals"),
+            (FilePurpose::FineTuneResults, "fine-tune-results"),
+            (FilePurpose::AssistantsOutput, "assistants_output"),
+            (FilePurpose::BatchOutput, "batch_output"),
+        ] {
+            assert_eq!(purpose.to_string(), wire);
+            assert_eq!(FilePurpose::from(wire), purpose);
+            assert_eq!(
+                serde_json::from_str::<FilePurpose>(&format!("\"{}\"", wire)).unwrap(),
+                purpose
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_purpose_is_read_but_not_sent() {
+        assert_eq!(FilePurpose::from("something_new"), FilePurpose::Other);
+        assert_eq!(
+            serde_json::from_str::<FilePurpose>("\"something_new\"").unwrap(),
+            FilePurpose::Other
+        );
+        assert!(FilePurpose::Other.validate().is_err());
+        assert!(
+            upload_form(b"data".to_vec(), "a.txt".to_owned(), &FilePurpose::Other)
+                .is_err()
+        );
+        assert!(upload_form(
+            b"data".to_vec(),
+            "a.txt".to_owned(),
+            &FilePurpose::UserData
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn upload_form_rejects_oversized_data() {
+        let size = MAX_FILE_BYTES as usize + 1;
+        match upload_form(vec![0; size], "big.jsonl".to_owned(), &FilePurpose::Batch) {
+            Err(APIError::Unknown(err)) => assert_eq!(
+                err,
+                format!(
+                    "file big.jsonl is {} bytes, exceeding the {} byte upload limit",
+                    size, MAX_FILE_BYTES
+                )
+            ),
+            other => panic!("expected an oversized upload error, got {:?}", other.err()),
+        }
+        assert!(upload_form(
+            b"{}".to_vec(),
+            "small.jsonl".to_owned(),
+            &FilePurpose::Batch
+        )
+        .is_ok());
+    }
+}
@@ -9,10 +9,13 @@
 //! - `FineTuningJobObject`: Struct representing a fine-tuning job object with various attributes.
 //! - `FineTuningJobError`: Struct for handling errors related to fine-tuning jobs.
 //! - `FineTuningJobEvent`: Struct for events associated with fine-tuning jobs.
+//! - `FineTuningCheckpoint` / `FineTuningCheckpointMetrics`: Structs for intermediate model snapshots of a job.
+//! - `FineTuningMethod`: Enum selecting the supervised or DPO fine-tuning method.
 //! - `HyperParameters`: Struct for specifying hyperparameters in fine-tuning jobs.
+//! - `IntOrAuto` / `FloatOrAuto`: Enums for hyperparameters that are either a number or `"auto"`.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
 use crate::impl_builder_methods;
@@ -33,6 +36,12 @@ pub struct CreateFineTuningJobRequest {
     /// Optional file containing validation data.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validation_file: Option<String>,
+    /// Optional seed for reproducible training runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// Optional fine-tuning method, such as supervised or DPO.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<FineTuningMethod>,
 }
 
 impl CreateFineTuningJobRequest {
@@ -44,6 +53,8 @@ impl CreateFineTuningJobRequest {
             hyperparameters: None,
             suffix: None,
             validation_file: None,
+            seed: None,
+            method: None,
         }
     }
 }
@@ -52,9 +63,80 @@ impl_builder_methods!(
     CreateFineTuningJobRequest,
     hyperparameters: HyperParameters,
     suffix: String,
-    validation_file: String
+    validation_file: String,
+    seed: i64,
+    method: FineTuningMethod
 );
 
+/// Represents the method used to fine-tune a model.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FineTuningMethod {
+    /// Supervised fine-tuning on prompt/completion examples.
+    Supervised {
+        /// Configuration of the supervised method.
+        supervised: SupervisedMethod,
+    },
+    /// Direct preference optimization on preferred/non-preferred pairs.
+    Dpo {
+        /// Configuration of the DPO method.
+        dpo: DpoMethod,
+    },
+}
+
+impl FineTuningMethod {
+    /// Creates a supervised method with the given hyperparameters.
+    pub fn supervised(hyperparameters: HyperParameters) -> Self {
+        FineTuningMethod::Supervised {
+            supervised: SupervisedMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+
+    /// Creates a DPO method with the given hyperparameters.
+    pub fn dpo(hyperparameters: DpoHyperParameters) -> Self {
+        FineTuningMethod::Dpo {
+            dpo: DpoMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+}
+
+/// Represents the configuration of the supervised fine-tuning method.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SupervisedMethod {
+    /// Optional hyperparameters for the supervised method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<HyperParameters>,
+}
+
+/// Represents the configuration of the DPO fine-tuning method.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DpoMethod {
+    /// Optional hyperparameters for the DPO method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<DpoHyperParameters>,
+}
+
+/// Represents hyperparameters for the DPO fine-tuning method.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DpoHyperParameters {
+    /// Optional weight of the penalty between the policy and reference model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beta: Option<FloatOrAuto>,
+    /// Optional batch size for the fine-tuning job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<IntOrAuto>,
+    /// Optional learning rate multiplier for the fine-tuning job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<FloatOrAuto>,
+    /// Optional number of epochs for the fine-tuning job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<IntOrAuto>,
+}
+
 /// Represents a request to list fine-tuning jobs.
 #[derive(Debug, Serialize, Default)]
 pub struct ListFineTuningJobsRequest {
@@ -202,16 +284,251 @@ pub struct FineTuningJobEvent {
     pub object: String,
 }
 
+/// Represents a model snapshot saved at a step of a fine-tuning job.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FineTuningCheckpoint {
+    /// Unique identifier for the checkpoint.
+    pub id: String,
+    /// Object type, typically "fine_tuning.job.checkpoint".
+    pub object: String,
+    /// Timestamp of when the checkpoint was created.
+    pub created_at: i64,
+    /// Name of the checkpoint model, usable like any fine-tuned model.
+    pub fine_tuned_model_checkpoint: String,
+    /// Identifier of the fine-tuning job that produced the checkpoint.
+    pub fine_tuning_job_id: String,
+    /// Step number at which the checkpoint was saved.
+    pub step_number: i64,
+    /// Training and validation metrics at the checkpoint's step.
+    pub metrics: FineTuningCheckpointMetrics,
+}
+
+/// Represents the metrics of a fine-tuning checkpoint; each is absent when not reported.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct FineTuningCheckpointMetrics {
+    /// Optional step the metrics were measured at.
+    #[serde(default)]
+    pub step: Option<f64>,
+    /// Optional training loss.
+    #[serde(default)]
+    pub train_loss: Option<f64>,
+    /// Optional mean token accuracy on the training batch.
+    #[serde(default)]
+    pub train_mean_token_accuracy: Option<f64>,
+    /// Optional validation loss on a sample of the validation file.
+    #[serde(default)]
+    pub valid_loss: Option<f64>,
+    /// Optional mean token accuracy on a sample of the validation file.
+    #[serde(default)]
+    pub valid_mean_token_accuracy: Option<f64>,
+    /// Optional validation loss on the full validation file.
+    #[serde(default)]
+    pub full_valid_loss: Option<f64>,
+    /// Optional mean token accuracy on the full validation file.
+    #[serde(default)]
+    pub full_valid_mean_token_accuracy: Option<f64>,
+}
+
 /// Represents hyperparameters for a fine-tuning job.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct HyperParameters {
     /// Optional batch size for the fine-tuning job.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub batch_size: Option<String>,
+    pub batch_size: Option<IntOrAuto>,
     /// Optional learning rate multiplier for the fine-tuning job.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub learning_rate_multiplier: Option<String>,
+    pub learning_rate_multiplier: Option<FloatOrAuto>,
     /// Optional number of epochs for the fine-tuning job.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub n_epochs: Option<String>,
+    pub n_epochs: Option<IntOrAuto>,
+}
+
+/// Wire value of a hyperparameter, either a number or the literal string `"auto"`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrAuto<T> {
+    /// Explicit numeric value.
+    Number(T),
+    /// String value, expected to be `"auto"`.
+    Text(String),
+}
+
+impl<T> NumberOrAuto<T> {
+    /// Converts the wire value into `Some(number)`, or `None` for `"auto"`.
+    fn into_number<E: de::Error>(self) -> Result<Option<T>, E> {
+        match self {
+            NumberOrAuto::Number(number) => Ok(Some(number)),
+            NumberOrAuto::Text(text) if text == "auto" => Ok(None),
+            NumberOrAuto::Text(text) => Err(E::invalid_value(
+                de::Unexpected::Str(&text),
+                &"a number or \"auto\"",
+            )),
+        }
+    }
+}
+
+/// Represents an integer hyperparameter that may be left for the API to choose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntOrAuto {
+    /// Let the API pick the value.
+    Auto,
+    /// Explicit integer value.
+    Int(i64),
+}
+
+impl Serialize for IntOrAuto {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            IntOrAuto::Auto => serializer.serialize_str("auto"),
+            IntOrAuto::Int(value) => serializer.serialize_i64(*value),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IntOrAuto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(NumberOrAuto::<i64>::deserialize(deserializer)?
+            .into_number()?
+            .map_or(IntOrAuto::Auto, IntOrAuto::Int))
+    }
+}
+
+/// Represents a floating-point hyperparameter that may be left for the API to choose.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatOrAuto {
+    /// Let the API pick the value.
+    Auto,
+    /// Explicit floating-point value.
+    Float(f64),
+}
+
+impl Serialize for FloatOrAuto {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FloatOrAuto::Auto => serializer.serialize_str("auto"),
+            FloatOrAuto::Float(value) => serializer.serialize_f64(*value),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FloatOrAuto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(NumberOrAuto::<f64>::deserialize(deserializer)?
+            .into_number()?
+            .map_or(FloatOrAuto::Auto, FloatOrAuto::Float))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_auto_hyperparameters() {
+        let params: HyperParameters = serde_json::from_str(
+            r#"{"batch_size": "auto", "learning_rate_multiplier": "auto", "n_epochs": "auto"}"#,
+        )
+        .unwrap();
+        assert_eq!(params.batch_size, Some(IntOrAuto::Auto));
+        assert_eq!(params.learning_rate_multiplier, Some(FloatOrAuto::Auto));
+        assert_eq!(params.n_epochs, Some(IntOrAuto::Auto));
+    }
+
+    #[test]
+    fn deserializes_numeric_hyperparameters() {
+        let params: HyperParameters = serde_json::from_str(
+            r#"{"batch_size": 4, "learning_rate_multiplier": 1.8, "n_epochs": 3}"#,
+        )
+        .unwrap();
+        assert_eq!(params.batch_size, Some(IntOrAuto::Int(4)));
+        assert_eq!(
+            params.learning_rate_multiplier,
+            Some(FloatOrAuto::Float(1.8))
+        );
+        assert_eq!(params.n_epochs, Some(IntOrAuto::Int(3)));
+    }
+
+    #[test]
+    fn rejects_unknown_hyperparameter_strings() {
+        assert!(serde_json::from_str::<IntOrAuto>(r#""many""#).is_err());
+    }
+
+    #[test]
+    fn serializes_hyperparameters_as_numbers_or_auto() {
+        let params = HyperParameters {
+            batch_size: Some(IntOrAuto::Auto),
+            learning_rate_multiplier: Some(FloatOrAuto::Float(0.5)),
+            n_epochs: Some(IntOrAuto::Int(2)),
+        };
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            serde_json::json!({
+                "batch_size": "auto",
+                "learning_rate_multiplier": 0.5,
+                "n_epochs": 2
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_seed_and_nested_supervised_method() {
+        let req = CreateFineTuningJobRequest::new(
+            "gpt-4o-mini-2024-07-18".to_owned(),
+            "file-abc123".to_owned(),
+        )
+        .seed(42)
+        .method(FineTuningMethod::supervised(HyperParameters {
+            n_epochs: Some(IntOrAuto::Int(3)),
+            ..HyperParameters::default()
+        }));
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "model": "gpt-4o-mini-2024-07-18",
+                "training_file": "file-abc123",
+                "seed": 42,
+                "method": {
+                    "type": "supervised",
+                    "supervised": { "hyperparameters": { "n_epochs": 3 } }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_checkpoint_page() {
+        let page: FineTuningPagination<FineTuningCheckpoint> = serde_json::from_str(
+            r#"{
+                "object": "list",
+                "data": [{
+                    "object": "fine_tuning.job.checkpoint",
+                    "id": "ftckpt_zc4Q7MP6XxulcVzj4MZdwsAB",
+                    "created_at": 1721764867,
+                    "fine_tuned_model_checkpoint": "ft:gpt-4o-mini-2024-07-18:my-org:custom-suffix:96olL566:ckpt-step-2000",
+                    "metrics": {
+                        "full_valid_loss": 0.134,
+                        "full_valid_mean_token_accuracy": 0.874
+                    },
+                    "fine_tuning_job_id": "ftjob-abc123",
+                    "step_number": 2000
+                }],
+                "first_id": "ftckpt_zc4Q7MP6XxulcVzj4MZdwsAB",
+                "last_id": "ftckpt_zc4Q7MP6XxulcVzj4MZdwsAB",
+                "has_more": false
+            }"#,
+        )
+        .unwrap();
+        assert!(!page.has_more);
+        let checkpoint = &page.data[0];
+        assert_eq!(checkpoint.step_number, 2000);
+        assert!(checkpoint
+            .fine_tuned_model_checkpoint
+            .ends_with(":ckpt-step-2000"));
+        assert_eq!(checkpoint.metrics.full_valid_loss, Some(0.134));
+        assert_eq!(
+            checkpoint.metrics.full_valid_mean_token_accuracy,
+            Some(0.874)
+        );
+        assert!(checkpoint.metrics.train_loss.is_none());
+    }
 }
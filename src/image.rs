@@ -1,25 +1,59 @@
 //! This module defines the structures and methods for image generation, editing, and variations.
 //! It includes:
-//! - `ImageData`: Struct representing the data of an image, such as its URL.
+//! - `ImageData`: Struct representing the data of an image, as a URL or base64-encoded bytes.
 //! - `ImageGenerationRequest`: Struct for creating a request to generate an image.
+//! - `ImageGenerationRequest::validate`: Method rejecting more than one image for DALL·E 3.
 //! - `ImageGenerationResponse`: Struct for the response from an image generation request.
 //! - `ImageEditRequest`: Struct for creating a request to edit an existing image.
 //! - `ImageEditResponse`: Struct for the response from an image edit request.
 //! - `ImageVariationRequest`: Struct for creating a request to generate variations of an image.
 //! - `ImageVariationResponse`: Struct for the response from an image variation request.
+//! - `image_mime_type`: Function mapping an image file extension to its MIME type.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::option::Option;
+use std::path::Path;
 
-use crate::impl_builder_methods;
+use crate::{error::APIError, impl_builder_methods};
 
-/// Represents the data of an image, such as its URL.
+/// Name of the DALL·E 3 model, which only generates one image per request.
+const DALL_E_3: &str = "dall-e-3";
+
+/// Name of the GPT Image model, which always returns base64-encoded images and accepts several
+/// reference images for edits.
+pub const GPT_IMAGE_1: &str = "gpt-image-1";
+
+/// Represents the data of an image, as a URL or base64-encoded bytes.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ImageData {
-    /// URL of the image.
-    pub url: String,
+    /// Optional URL of the image, returned when `response_format` is `url`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Optional base64-encoded image, returned when `response_format` is `b64_json` and
+    /// always returned by `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub b64_json: Option<String>,
+    /// Optional prompt DALL·E 3 actually used after rewriting the original prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+}
+
+impl ImageData {
+    /// Decodes the base64-encoded image, if the response carried one.
+    pub fn decode_b64(&self) -> Result<Option<Vec<u8>>, APIError> {
+        self.b64_json
+            .as_deref()
+            .map(|data| {
+                STANDARD.decode(data).map_err(|err| {
+                    APIError::Unknown(format!("invalid b64_json: {}", err))
+                })
+            })
+            .transpose()
+    }
 }
 
 /// Represents a request to generate an image.
@@ -42,6 +76,12 @@ pub struct ImageGenerationRequest {
     /// Optional user identifier.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+    /// Optional quality of the image, `standard` or `hd`; DALL·E 3 only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// Optional style of the image, `vivid` or `natural`; DALL·E 3 only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
 }
 
 impl ImageGenerationRequest {
@@ -54,6 +94,33 @@ impl ImageGenerationRequest {
             size: None,
             response_format: None,
             user: None,
+            quality: None,
+            style: None,
+        }
+    }
+
+    /// Creates a new `ImageGenerationRequest` for DALL·E 3 with the given quality and style.
+    pub fn dall_e_3(prompt: String, quality: String, style: String) -> Self {
+        Self::new(prompt)
+            .model(DALL_E_3.to_owned())
+            .quality(quality)
+            .style(style)
+    }
+
+    /// Creates a new `ImageGenerationRequest` for `gpt-image-1`, whose images are always
+    /// returned in `b64_json`.
+    pub fn gpt_image_1(prompt: String) -> Self {
+        Self::new(prompt).model(GPT_IMAGE_1.to_owned())
+    }
+
+    /// Checks that DALL·E 3 requests ask for a single image, as the model rejects `n > 1`.
+    pub fn validate(&self) -> Result<(), APIError> {
+        match (self.model.as_deref(), self.n) {
+            (Some(DALL_E_3), Some(n)) if n > 1 => Err(APIError::Unknown(format!(
+                "{} only supports n = 1, got {}",
+                DALL_E_3, n
+            ))),
+            _ => Ok(()),
         }
     }
 }
@@ -64,7 +131,9 @@ impl_builder_methods!(
     n: i32,
     size: String,
     response_format: String,
-    user: String
+    user: String,
+    quality: String,
+    style: String
 );
 
 /// Represents the response from an image generation request.
@@ -81,9 +150,12 @@ pub struct ImageGenerationResponse {
 /// Represents a request to edit an existing image.
 #[derive(Debug, Serialize, Clone)]
 pub struct ImageEditRequest {
-    /// Image to be edited.
+    /// Path to the image to be edited.
     pub image: String,
-    /// Optional mask to be applied to the image.
+    /// Optional paths to further reference images, sent alongside `image`; `gpt-image-1` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_images: Option<Vec<String>>,
+    /// Optional path to a mask to be applied to the image.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mask: Option<String>,
     /// Prompt for editing the image.
@@ -110,6 +182,7 @@ impl ImageEditRequest {
     pub fn new(image: String, prompt: String) -> Self {
         Self {
             image,
+            reference_images: None,
             prompt,
             mask: None,
             model: None,
@@ -119,10 +192,48 @@ impl ImageEditRequest {
             user: None,
         }
     }
+
+    /// Builds the multipart form for this request, reading the images from disk.
+    ///
+    /// With reference images, all images are sent as `image[]`; otherwise the single image is
+    /// sent as `image`.
+    pub async fn form(&self) -> Result<Form, APIError> {
+        let mut form = Form::new();
+        match &self.reference_images {
+            Some(reference_images) => {
+                form = form.part("image[]", image_part(&self.image).await?);
+                for reference_image in reference_images {
+                    form = form.part("image[]", image_part(reference_image).await?);
+                }
+            }
+            None => form = form.part("image", image_part(&self.image).await?),
+        }
+        if let Some(mask) = &self.mask {
+            form = form.part("mask", image_part(mask).await?);
+        }
+        form = form.text("prompt", self.prompt.clone());
+        if let Some(model) = &self.model {
+            form = form.text("model", model.clone());
+        }
+        if let Some(n) = self.n {
+            form = form.text("n", n.to_string());
+        }
+        if let Some(size) = &self.size {
+            form = form.text("size", size.clone());
+        }
+        if let Some(response_format) = &self.response_format {
+            form = form.text("response_format", response_format.clone());
+        }
+        if let Some(user) = &self.user {
+            form = form.text("user", user.clone());
+        }
+        Ok(form)
+    }
 }
 
 impl_builder_methods!(
     ImageEditRequest,
+    reference_images: Vec<String>,
     mask: String,
     model: String,
     n: i32,
@@ -145,7 +256,7 @@ pub struct ImageEditResponse {
 /// Represents a request to generate variations of an image.
 #[derive(Debug, Serialize, Clone)]
 pub struct ImageVariationRequest {
-    /// Image to generate variations for.
+    /// Path to the image to generate variations for.
     pub image: String,
     /// Optional number of variations to generate.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -176,6 +287,27 @@ impl ImageVariationRequest {
             user: None,
         }
     }
+
+    /// Builds the multipart form for this request, reading the image from disk.
+    pub async fn form(&self) -> Result<Form, APIError> {
+        let mut form = Form::new().part("image", image_part(&self.image).await?);
+        if let Some(model) = &self.model {
+            form = form.text("model", model.clone());
+        }
+        if let Some(n) = self.n {
+            form = form.text("n", n.to_string());
+        }
+        if let Some(size) = &self.size {
+            form = form.text("size", size.clone());
+        }
+        if let Some(response_format) = &self.response_format {
+            form = form.text("response_format", response_format.clone());
+        }
+        if let Some(user) = &self.user {
+            form = form.text("user", user.clone());
+        }
+        Ok(form)
+    }
 }
 
 impl_builder_methods!(
@@ -197,3 +329,135 @@ pub struct ImageVariationResponse {
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
+
+/// Returns the MIME type of an image file from its extension, or `application/octet-stream`
+/// for extensions that are not a supported image format.
+pub fn image_mime_type(path: &str) -> &'static str {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Reads an image file from disk into a multipart part named after the file, with the MIME
+/// type matching its extension.
+async fn image_part(path: &str) -> Result<Part, APIError> {
+    let bytes = async_std::fs::read(path).await?;
+    let filename = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_owned());
+    Ok(Part::bytes(bytes)
+        .file_name(filename)
+        .mime_str(image_mime_type(path))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate_rejects_several_dall_e_3_images() {
+        let req = ImageGenerationRequest::new("A red fox".to_owned())
+            .model(DALL_E_3.to_owned());
+        assert!(req.clone().n(1).validate().is_ok());
+        match req.n(2).validate() {
+            Err(APIError::Unknown(err)) => {
+                assert_eq!(err, "dall-e-3 only supports n = 1, got 2")
+            }
+            other => panic!("expected an n error, got {:?}", other),
+        }
+        let req = ImageGenerationRequest::new("A red fox".to_owned()).n(4);
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn serializes_dall_e_3_quality_and_style() {
+        let req = ImageGenerationRequest::dall_e_3(
+            "A red fox".to_owned(),
+            "hd".to_owned(),
+            "natural".to_owned(),
+        );
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({
+                "prompt": "A red fox",
+                "model": "dall-e-3",
+                "quality": "hd",
+                "style": "natural"
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_revised_prompt() {
+        let data: ImageData = serde_json::from_value(json!({
+            "url": "https://example.com/fox.png",
+            "revised_prompt": "A photorealistic red fox in a snowy forest"
+        }))
+        .unwrap();
+        assert_eq!(
+            data.revised_prompt.as_deref(),
+            Some("A photorealistic red fox in a snowy forest")
+        );
+
+        let data: ImageData =
+            serde_json::from_value(json!({ "url": "https://example.com/fox.png" }))
+                .unwrap();
+        assert!(data.revised_prompt.is_none());
+    }
+
+    fn image_data(b64_json: Option<&str>) -> ImageData {
+        ImageData {
+            url: None,
+            b64_json: b64_json.map(str::to_owned),
+            revised_prompt: None,
+        }
+    }
+
+    #[test]
+    fn decode_b64_decodes_valid_data() {
+        assert_eq!(
+            image_data(Some("iVBORw==")).decode_b64().unwrap(),
+            Some(vec![0x89, b'P', b'N', b'G'])
+        );
+    }
+
+    #[test]
+    fn decode_b64_rejects_invalid_data() {
+        match image_data(Some("not base64!")).decode_b64() {
+            Err(APIError::Unknown(err)) => {
+                assert!(err.starts_with("invalid b64_json"), "{}", err)
+            }
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decode_b64_without_data_returns_none() {
+        assert_eq!(image_data(None).decode_b64().unwrap(), None);
+    }
+
+    #[test]
+    fn image_mime_type_follows_extension() {
+        for (path, mime) in [
+            ("fox.png", "image/png"),
+            ("fox.jpg", "image/jpeg"),
+            ("photos/fox.JPEG", "image/jpeg"),
+            ("fox.webp", "image/webp"),
+            ("fox.gif", "image/gif"),
+            ("fox.bmp", "application/octet-stream"),
+            ("fox", "application/octet-stream"),
+        ] {
+            assert_eq!(image_mime_type(path), mime, "{}", path);
+        }
+    }
+}
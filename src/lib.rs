@@ -8,6 +8,13 @@ pub mod assistant;
 /// Module for audio processing, including transcription, translation, and speech synthesis.
 pub mod audio;
 
+/// Module for submitting and managing asynchronous batch jobs.
+pub mod batch;
+
+/// Module for a blocking facade over the asynchronous client.
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 /// Module for handling chat completion requests and responses.
 pub mod chat_completion;
 
@@ -44,11 +51,31 @@ pub mod message;
 /// Module defining various AI models.
 pub mod models;
 
+/// Module for a local mock server used to test code built on the client.
+#[cfg(feature = "mock")]
+pub mod mock;
+
 /// Module for moderation checks and responses.
 pub mod moderation;
 
+/// Module for reading the rate limit state reported by the API.
+pub mod ratelimit;
+
+/// Module for creating and managing model responses.
+pub mod responses;
+
 /// Module for creating and managing runs.
 pub mod run;
 
+/// Module for parsing server-sent event streams.
+mod stream;
+
 /// Module for creating and managing threads.
 pub mod thread;
+
+/// Module for counting tokens locally before sending requests.
+#[cfg(feature = "tokenizer")]
+pub mod tokenizer;
+
+/// Module for managing vector stores used by assistant file search.
+pub mod vector_store;
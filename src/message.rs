@@ -1,16 +1,22 @@
 //! This module defines the structures and methods for creating, modifying, and managing messages and their related content.
 //! It includes:
-//! - `CreateMessageRequest`: Struct for creating a new message with optional file IDs and metadata.
+//! - `CreateMessageRequest`: Struct for creating a new message with optional attachments and metadata.
+//! - `MessageContent`: Enum for message content given as a string or an array of content blocks.
+//! - `MessageContentBlock`: Enum for the text and image content blocks sent in a message.
+//! - `Attachment`: Struct for a file attached to a message along with the tools it is added to.
 //! - `ModifyMessageRequest`: Struct for modifying an existing message's metadata.
 //! - `MessageObject`: Struct representing a message object with various attributes.
-//! - `Content`: Struct for the content of a message.
+//! - `Content`: Enum for the text and image file content blocks of a message.
+//! - `ImageFile`: Struct for an image file referenced in a message.
 //! - `ContentText`: Struct for text content within a message, including annotations.
+//! - `Annotation`: Enum for file citation and file path annotations on message text.
+//! - `MessageDeltaObject`: Struct for incremental message changes emitted by streamed runs.
 //! - `ListMessage`: Struct for listing multiple messages.
 //! - `MessageFileObject`: Struct representing a file object associated with a message.
 //! - `ListMessageFile`: Struct for listing multiple message file objects.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
-use crate::common::MessageRole;
+use crate::common::{MessageRole, ObjectType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -21,11 +27,14 @@ use crate::impl_builder_methods;
 pub struct CreateMessageRequest {
     /// Role of the message sender.
     pub role: MessageRole,
-    /// Content of the message.
-    pub content: String,
-    /// Optional file IDs associated with the message.
+    /// Content of the message, as text or content blocks.
+    pub content: MessageContent,
+    /// Optional file IDs associated with the message; superseded by `attachments` in the v2 API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_ids: Option<Vec<String>>,
+    /// Optional files attached to the message, along with the tools they are added to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
     /// Optional metadata for the message.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
@@ -33,11 +42,12 @@ pub struct CreateMessageRequest {
 
 impl CreateMessageRequest {
     /// Creates a new `CreateMessageRequest` with the specified role and content.
-    pub fn new(role: MessageRole, content: String) -> Self {
+    pub fn new(role: MessageRole, content: impl Into<MessageContent>) -> Self {
         Self {
             role,
-            content,
+            content: content.into(),
             file_ids: None,
+            attachments: None,
             metadata: None,
         }
     }
@@ -46,9 +56,98 @@ impl CreateMessageRequest {
 impl_builder_methods!(
     CreateMessageRequest,
     file_ids: Vec<String>,
+    attachments: Vec<Attachment>,
     metadata: HashMap<String, String>
 );
 
+/// Represents the content of a message to create.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum MessageContent {
+    /// Plain text content.
+    Text(String),
+    /// Array of text and image content blocks.
+    Blocks(Vec<MessageContentBlock>),
+}
+
+impl From<String> for MessageContent {
+    /// Converts a string into `MessageContent::Text`.
+    fn from(text: String) -> Self {
+        MessageContent::Text(text)
+    }
+}
+
+impl From<&str> for MessageContent {
+    /// Converts a string into `MessageContent::Text`.
+    fn from(text: &str) -> Self {
+        MessageContent::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<MessageContentBlock>> for MessageContent {
+    /// Converts a list of content blocks into `MessageContent::Blocks`.
+    fn from(blocks: Vec<MessageContentBlock>) -> Self {
+        MessageContent::Blocks(blocks)
+    }
+}
+
+/// Represents a content block of a message to create, tagged on its `type`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageContentBlock {
+    /// Text content.
+    Text {
+        /// Text of the block.
+        text: String,
+    },
+    /// Image file uploaded with the `vision` purpose.
+    ImageFile {
+        /// Details of the referenced image file.
+        image_file: ImageFile,
+    },
+    /// Image referenced by URL.
+    ImageUrl {
+        /// Details of the referenced image.
+        image_url: MessageImageUrl,
+    },
+}
+
+/// Represents an image referenced by URL in a message.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MessageImageUrl {
+    /// URL of the image.
+    pub url: String,
+    /// Optional level of detail, `low`, `high` or `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Represents a file attached to a message along with the tools it is added to.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Attachment {
+    /// Identifier of the attached file.
+    pub file_id: String,
+    /// Tools the file is added to.
+    pub tools: Vec<AttachmentTool>,
+}
+
+impl Attachment {
+    /// Creates a new `Attachment` adding the file to the given tools.
+    pub fn new(file_id: String, tools: Vec<AttachmentTool>) -> Self {
+        Self { file_id, tools }
+    }
+}
+
+/// Represents a tool a message attachment is added to, tagged on its `type`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttachmentTool {
+    /// Makes the file available to the code interpreter.
+    CodeInterpreter,
+    /// Indexes the file for file search.
+    FileSearch,
+}
+
 /// Represents a request to modify an existing message's metadata.
 #[derive(Debug, Serialize, Clone)]
 pub struct ModifyMessageRequest {
@@ -81,8 +180,8 @@ impl_builder_methods!(
 pub struct MessageObject {
     /// Unique identifier for the message.
     pub id: String,
-    /// Object type, typically "message".
-    pub object: String,
+    /// Object type, typically `ObjectType::ThreadMessage`.
+    pub object: ObjectType,
     /// Timestamp of when the message was created.
     pub created_at: i64,
     /// Identifier for the associated thread.
@@ -97,22 +196,39 @@ pub struct MessageObject {
     /// Optional identifier for the run.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub run_id: Option<String>,
-    /// File IDs associated with the message.
+    /// File IDs associated with the message, only returned by the v1 API.
+    #[serde(default)]
     pub file_ids: Vec<String>,
+    /// Optional files attached to the message, returned by the v2 API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
     /// Metadata for the message.
     pub metadata: HashMap<String, String>,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
 
-/// Represents the content of a message.
+/// Represents a content block of a message, tagged on its `type`.
 #[derive(Debug, Deserialize, Serialize)]
-pub struct Content {
-    /// Type of the content.
-    #[serde(rename = "type")]
-    pub content_type: String,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Content {
     /// Text content of the message.
-    pub text: ContentText,
+    Text {
+        /// Text value and its annotations.
+        text: ContentText,
+    },
+    /// Image file referenced by the message.
+    ImageFile {
+        /// Details of the referenced image file.
+        image_file: ImageFile,
+    },
+}
+
+/// Represents an image file referenced in a message.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ImageFile {
+    /// Identifier of the image file.
+    pub file_id: String,
 }
 
 /// Represents text content within a message, including annotations.
@@ -121,7 +237,95 @@ pub struct ContentText {
     /// Text value of the content.
     pub value: String,
     /// Annotations for the text content.
-    pub annotations: Vec<String>,
+    pub annotations: Vec<Annotation>,
+}
+
+/// Represents an annotation on a span of message text.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Annotation {
+    /// Citation pointing to a quote from a file used by the assistant.
+    FileCitation {
+        /// Text in the message content being annotated.
+        text: String,
+        /// Start offset of the annotated text.
+        start_index: u32,
+        /// End offset of the annotated text.
+        end_index: u32,
+        /// Details of the cited file.
+        file_citation: FileCitation,
+    },
+    /// Path to a file generated by the assistant.
+    FilePath {
+        /// Text in the message content being annotated.
+        text: String,
+        /// Start offset of the annotated text.
+        start_index: u32,
+        /// End offset of the annotated text.
+        end_index: u32,
+        /// Details of the generated file.
+        file_path: FilePath,
+    },
+}
+
+/// Represents the file cited by a `file_citation` annotation.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FileCitation {
+    /// Identifier of the cited file.
+    pub file_id: String,
+    /// Optional quote from the cited file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<String>,
+}
+
+/// Represents the file referenced by a `file_path` annotation.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FilePath {
+    /// Identifier of the generated file.
+    pub file_id: String,
+}
+
+/// Represents an incremental change to a message, emitted while a run streams.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MessageDeltaObject {
+    /// Identifier of the message being updated.
+    pub id: String,
+    /// Object type, typically `ObjectType::ThreadMessageDelta`.
+    pub object: ObjectType,
+    /// Fields of the message that changed.
+    pub delta: MessageDelta,
+}
+
+/// Represents the fields of a message that changed in a delta.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MessageDelta {
+    /// Optional role of the message sender.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<MessageRole>,
+    /// Content blocks that changed.
+    #[serde(default)]
+    pub content: Vec<MessageDeltaContent>,
+}
+
+/// Represents a changed content block within a message delta.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MessageDeltaContent {
+    /// Index of the content block in the message.
+    pub index: u32,
+    /// Type of the content block.
+    #[serde(rename = "type")]
+    pub content_type: String,
+    /// Optional text appended to the content block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<TextDelta>,
+}
+
+/// Represents text appended to a content block.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TextDelta {
+    /// Optional text fragment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
 }
 
 /// Represents a list of messages.
@@ -172,3 +376,109 @@ pub struct ListMessageFile {
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a v2 assistant message JSON with the given content blocks.
+    fn message_json(content: &str) -> String {
+        format!(
+            r#"{{
+                "id": "msg_abc123",
+                "object": "thread.message",
+                "created_at": 1699017614,
+                "thread_id": "thread_abc123",
+                "role": "assistant",
+                "content": {},
+                "assistant_id": "asst_abc123",
+                "run_id": "run_abc123",
+                "attachments": [],
+                "metadata": {{}}
+            }}"#,
+            content
+        )
+    }
+
+    #[test]
+    fn deserializes_file_citation_annotation() {
+        let message: MessageObject = serde_json::from_str(&message_json(
+            r#"[{
+                "type": "text",
+                "text": {
+                    "value": "The answer is 42【4:0†source】.",
+                    "annotations": [{
+                        "type": "file_citation",
+                        "text": "【4:0†source】",
+                        "start_index": 14,
+                        "end_index": 26,
+                        "file_citation": { "file_id": "file-abc123" }
+                    }]
+                }
+            }]"#,
+        ))
+        .unwrap();
+        let Content::Text { text } = &message.content[0] else {
+            panic!("expected a text block, got {:?}", message.content[0]);
+        };
+        match &text.annotations[..] {
+            [Annotation::FileCitation {
+                text,
+                start_index,
+                end_index,
+                file_citation,
+            }] => {
+                assert_eq!(text, "【4:0†source】");
+                assert_eq!((*start_index, *end_index), (14, 26));
+                assert_eq!(file_citation.file_id, "file-abc123");
+                assert!(file_citation.quote.is_none());
+            }
+            other => panic!("expected one file citation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserializes_text_and_image_file_blocks() {
+        let message: MessageObject = serde_json::from_str(&message_json(
+            r#"[
+                { "type": "text", "text": { "value": "Here is the chart.", "annotations": [] } },
+                { "type": "image_file", "image_file": { "file_id": "file-img123" } }
+            ]"#,
+        ))
+        .unwrap();
+        assert_eq!(message.content.len(), 2);
+        match &message.content[0] {
+            Content::Text { text } => {
+                assert_eq!(text.value, "Here is the chart.");
+                assert!(text.annotations.is_empty());
+            }
+            other => panic!("expected a text block, got {:?}", other),
+        }
+        match &message.content[1] {
+            Content::ImageFile { image_file } => {
+                assert_eq!(image_file.file_id, "file-img123")
+            }
+            other => panic!("expected an image_file block, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn serializes_attachments_with_tagged_tools() {
+        let req = CreateMessageRequest::new(MessageRole::User, "Summarize this report.")
+            .attachments(vec![Attachment::new(
+                "file-abc123".to_owned(),
+                vec![AttachmentTool::CodeInterpreter, AttachmentTool::FileSearch],
+            )]);
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "role": "user",
+                "content": "Summarize this report.",
+                "attachments": [{
+                    "file_id": "file-abc123",
+                    "tools": [{ "type": "code_interpreter" }, { "type": "file_search" }]
+                }]
+            })
+        );
+    }
+}
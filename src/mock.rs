@@ -0,0 +1,405 @@
+//! This module provides a local HTTP server that answers requests with canned responses, so
+//! client behavior can be exercised without reaching the real API.
+//! It includes:
+//! - `MockServer`: Struct running the server and registering the responses it returns.
+//! - `RecordedRequest`: Struct describing a request received by the server.
+
+use crate::{
+    client::{Client, ClientBuilder},
+    error::APIError,
+};
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+/// Represents a request received by a `MockServer`.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// HTTP method of the request.
+    pub method: Method,
+    /// Path of the request, including any query string.
+    pub path: String,
+    /// Headers of the request, with lowercase names.
+    pub headers: HashMap<String, String>,
+    /// Body of the request.
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Deserializes the body of the request as JSON.
+    pub fn json(&self) -> Result<Value, APIError> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// Canned response returned for a method and path.
+#[derive(Debug, Clone)]
+struct MockResponse {
+    method: Method,
+    path: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// State shared between a `MockServer` and the task accepting its connections.
+#[derive(Debug, Default)]
+struct MockState {
+    responses: Vec<MockResponse>,
+    requests: Vec<RecordedRequest>,
+    delay: Duration,
+}
+
+/// Represents a local HTTP server answering requests with registered canned responses.
+///
+/// Responses are matched on method and path, ignoring the query string, with the most recently
+/// registered response winning. Unmatched requests get a 404 in the API's error format. The
+/// server shuts down when dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    /// Address the server listens on.
+    pub addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server listening on a free local port.
+    pub async fn start() -> Result<Self, APIError> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let task_state = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&task_state)));
+            }
+        });
+        Ok(Self { addr, state, task })
+    }
+
+    /// Returns the base URL of the server, suitable for `ClientBuilder::endpoint`.
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns a `ClientBuilder` with a dummy API key pointed at the server.
+    pub fn client_builder(&self) -> ClientBuilder {
+        ClientBuilder::new("test-key".to_owned()).endpoint(self.uri())
+    }
+
+    /// Builds a `Client` with a dummy API key pointed at the server.
+    pub fn client(&self) -> Result<Client, APIError> {
+        self.client_builder().build()
+    }
+
+    /// Registers a raw response body returned for `method` requests to `path`.
+    pub fn mock(&self, method: Method, path: &str, status: u16, body: impl Into<String>) {
+        self.mock_with_headers(method, path, status, Vec::new(), body);
+    }
+
+    /// Registers a raw response body with extra response headers, such as rate limit headers.
+    pub fn mock_with_headers(
+        &self,
+        method: Method,
+        path: &str,
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: impl Into<String>,
+    ) {
+        self.lock().responses.push(MockResponse {
+            method,
+            path: path.to_owned(),
+            status,
+            headers,
+            body: body.into(),
+        });
+    }
+
+    /// Registers a JSON response returned for `method` requests to `path`.
+    pub fn mock_json<T: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        status: u16,
+        body: &T,
+    ) -> Result<(), APIError> {
+        self.mock(method, path, status, serde_json::to_string(body)?);
+        Ok(())
+    }
+
+    /// Registers an error response in the API's error format for `method` requests to `path`.
+    pub fn mock_error(&self, method: Method, path: &str, status: u16, message: &str) {
+        let body = json!({
+            "error": {
+                "message": message,
+                "type": "invalid_request_error",
+                "param": null,
+                "code": null
+            }
+        });
+        self.mock(method, path, status, body.to_string());
+    }
+
+    /// Registers a chat completion whose single choice answers with `content`.
+    pub fn mock_chat_completion(&self, content: &str) {
+        let body = json!({
+            "id": "chatcmpl-mock",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 }
+        });
+        self.mock(Method::POST, "/chat/completions", 200, body.to_string());
+    }
+
+    /// Registers a chat completion stream emitting `deltas` as content chunks, followed by a
+    /// `stop` chunk and the `[DONE]` marker.
+    pub fn mock_chat_completion_stream(&self, deltas: &[&str]) {
+        let chunk = |delta: Value, finish_reason: Value| {
+            let chunk = json!({
+                "id": "chatcmpl-mock",
+                "object": "chat.completion.chunk",
+                "created": 0,
+                "model": "gpt-4o",
+                "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }]
+            });
+            format!("data: {}\n\n", chunk)
+        };
+        let mut body = chunk(json!({ "role": "assistant", "content": "" }), Value::Null);
+        for delta in deltas {
+            body.push_str(&chunk(json!({ "content": delta }), Value::Null));
+        }
+        body.push_str(&chunk(json!({}), json!("stop")));
+        body.push_str("data: [DONE]\n\n");
+        self.mock_with_headers(
+            Method::POST,
+            "/chat/completions",
+            200,
+            vec![("content-type".to_owned(), "text/event-stream".to_owned())],
+            body,
+        );
+    }
+
+    /// Registers an embedding response with one embedding per vector in `embeddings`.
+    pub fn mock_embedding(&self, embeddings: &[Vec<f32>]) {
+        let data: Vec<Value> = embeddings
+            .iter()
+            .enumerate()
+            .map(|(index, embedding)| {
+                json!({ "object": "embedding", "embedding": embedding, "index": index })
+            })
+            .collect();
+        let body = json!({
+            "object": "list",
+            "data": data,
+            "model": "text-embedding-3-small",
+            "usage": { "prompt_tokens": 1, "total_tokens": 1 }
+        });
+        self.mock(Method::POST, "/embeddings", 200, body.to_string());
+    }
+
+    /// Delays every response by `delay` after the request is read, to exercise timeouts and
+    /// cancellation.
+    pub fn set_response_delay(&self, delay: Duration) {
+        self.lock().delay = delay;
+    }
+
+    /// Returns the requests received so far, oldest first.
+    pub fn received_requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Locks the shared state, recovering it if a connection task panicked.
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for MockServer {
+    /// Stops accepting connections.
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Reads a single request from `stream`, records it and writes the matching response.
+async fn serve(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let Ok(Some(request)) = read_request(&mut stream).await else {
+        return;
+    };
+    let (response, delay) = {
+        let mut state = state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = request.path.split('?').next().unwrap_or_default();
+        let response = state
+            .responses
+            .iter()
+            .rev()
+            .find(|response| response.method == request.method && response.path == path)
+            .cloned();
+        state.requests.push(request);
+        (response, state.delay)
+    };
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    let response = response.unwrap_or_else(|| MockResponse {
+        method: Method::GET,
+        path: String::new(),
+        status: 404,
+        headers: Vec::new(),
+        body: json!({
+            "error": {
+                "message": "no mock response registered for this request",
+                "type": "invalid_request_error",
+                "param": null,
+                "code": null
+            }
+        })
+        .to_string(),
+    });
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\ncontent-length: {}\r\nconnection: close\r\n",
+        response.status,
+        reqwest::StatusCode::from_u16(response.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default(),
+        response.body.len()
+    );
+    if !response
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+    {
+        head.push_str("content-type: application/json\r\n");
+    }
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(response.body.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Reads the request line, headers and body of an HTTP/1.1 request, returning `None` if the
+/// connection closes before a full request arrives.
+async fn read_request(
+    stream: &mut TcpStream,
+) -> std::io::Result<Option<RecordedRequest>> {
+    let mut buffer = Vec::new();
+    let head_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos;
+        }
+        if !read_more(stream, &mut buffer).await? {
+            return Ok(None);
+        }
+    };
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default();
+    let Ok(method) = Method::from_bytes(method.as_bytes()) else {
+        return Ok(None);
+    };
+    let path = request_line.next().unwrap_or_default().to_owned();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_owned()))
+        .collect();
+
+    let mut body = buffer.split_off(head_end + 4);
+    if headers
+        .get("transfer-encoding")
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"))
+    {
+        body = match read_chunked(stream, body).await? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+    } else {
+        let length = headers
+            .get("content-length")
+            .and_then(|length| length.parse::<usize>().ok())
+            .unwrap_or_default();
+        while body.len() < length {
+            if !read_more(stream, &mut body).await? {
+                return Ok(None);
+            }
+        }
+        body.truncate(length);
+    }
+    Ok(Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+/// Decodes a chunked request body, starting from the bytes already read in `pending`.
+async fn read_chunked(
+    stream: &mut TcpStream,
+    mut pending: Vec<u8>,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = loop {
+            if let Some(pos) = pending.windows(2).position(|window| window == b"\r\n") {
+                break pos;
+            }
+            if !read_more(stream, &mut pending).await? {
+                return Ok(None);
+            }
+        };
+        let size_line = String::from_utf8_lossy(&pending[..line_end]).into_owned();
+        let size_field = size_line.split(';').next().unwrap_or_default().trim();
+        let Ok(size) = usize::from_str_radix(size_field, 16) else {
+            return Ok(None);
+        };
+        pending.drain(..line_end + 2);
+        if size == 0 {
+            return Ok(Some(body));
+        }
+        while pending.len() < size + 2 {
+            if !read_more(stream, &mut pending).await? {
+                return Ok(None);
+            }
+        }
+        body.extend_from_slice(&pending[..size]);
+        pending.drain(..size + 2);
+    }
+}
+
+/// Reads more bytes from `stream` into `buffer`, returning `false` once the connection closes.
+async fn read_more(
+    stream: &mut TcpStream,
+    buffer: &mut Vec<u8>,
+) -> std::io::Result<bool> {
+    let mut chunk = [0; 4096];
+    let read = stream.read(&mut chunk).await?;
+    buffer.extend_from_slice(&chunk[..read]);
+    Ok(read > 0)
+}
@@ -1,7 +1,8 @@
-//! This module defines various enums and structs representing different AI models, such as GPT-4, GPT-3, Dalle, Whisper, Clip, and Embeddings models.
+//! This module defines various enums and structs representing different AI models, such as GPT-4, GPT-3, the o-series, Dalle, Whisper, Clip, and Embeddings models.
 //! Each enum variant corresponds to a specific model version or type, providing detailed information about the available models.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
 use strum::{AsRefStr, Display, EnumString};
 
 /// Enum representing different versions of the GPT-4 model.
@@ -26,6 +27,49 @@ pub enum GPT4 {
     /// An optimized version of GPT-4, referred to as GPT-4o.
     #[strum(serialize = "gpt-4o")]
     GPT4o,
+
+    /// A smaller, faster and cheaper version of GPT-4o.
+    #[strum(serialize = "gpt-4o-mini")]
+    GPT4oMini,
+
+    /// GPT-4.1, with improved instruction following and a longer context window.
+    #[strum(serialize = "gpt-4.1")]
+    GPT41,
+
+    /// A smaller, faster version of GPT-4.1.
+    #[strum(serialize = "gpt-4.1-mini")]
+    GPT41Mini,
+
+    /// The smallest and fastest version of GPT-4.1.
+    #[strum(serialize = "gpt-4.1-nano")]
+    GPT41Nano,
+}
+
+/// Enum representing the o-series reasoning models.
+///
+/// These models reject sampling parameters such as `temperature` and expect the output budget to
+/// be set through `max_completion_tokens` rather than `max_tokens`.
+#[derive(EnumString, Debug, Serialize, Deserialize, AsRefStr, Clone, Display)]
+pub enum OSeries {
+    /// The o1 reasoning model.
+    #[strum(serialize = "o1")]
+    O1,
+
+    /// A smaller, faster version of o1.
+    #[strum(serialize = "o1-mini")]
+    O1Mini,
+
+    /// The o3 reasoning model.
+    #[strum(serialize = "o3")]
+    O3,
+
+    /// A smaller, faster version of o3.
+    #[strum(serialize = "o3-mini")]
+    O3Mini,
+
+    /// A smaller, faster version of o4.
+    #[strum(serialize = "o4-mini")]
+    O4Mini,
 }
 
 /// Enum representing different versions of the GPT-3 model.
@@ -48,12 +92,15 @@ pub enum GPT3 {
 #[derive(EnumString, Debug, Serialize, Deserialize, AsRefStr, Clone, Display)]
 pub enum Dalle {
     /// Dalle 2, known for generating high-quality images from textual descriptions.
+    #[strum(serialize = "dall-e-2")]
     Dalle2,
 
     /// Dalle Mini, a smaller, more lightweight version of the Dalle model.
+    #[strum(serialize = "dalle-mini")]
     DalleMini,
 
     /// Dalle Mega, a larger version of the Dalle model for more complex image generation.
+    #[strum(serialize = "dalle-mega")]
     DalleMega,
 }
 
@@ -61,15 +108,19 @@ pub enum Dalle {
 #[derive(EnumString, Debug, Serialize, Deserialize, Clone, Display)]
 pub enum Whisper {
     /// Large version of the Whisper model, offering high accuracy.
+    #[strum(serialize = "whisper-large")]
     WhisperLarge,
 
     /// Medium version of the Whisper model, balancing performance and resource usage.
+    #[strum(serialize = "whisper-medium")]
     WhisperMedium,
 
     /// Small version of the Whisper model, optimized for speed and efficiency.
+    #[strum(serialize = "whisper-small")]
     WhisperSmall,
 
     /// Tiny version of the Whisper model, suitable for lightweight applications.
+    #[strum(serialize = "whisper-tiny")]
     WhisperTiny,
 }
 
@@ -77,6 +128,7 @@ pub enum Whisper {
 #[derive(EnumString, Debug, Serialize, Deserialize, Clone, Display)]
 pub enum ClipModels {
     /// Clip model with the VitBasePatch32 architecture, used for creating embeddings from images and text.
+    #[strum(serialize = "clip-vit-base-patch32")]
     ClipVitBasePatch32,
 }
 
@@ -84,29 +136,57 @@ pub enum ClipModels {
 #[derive(EnumString, Debug, Serialize, Deserialize, Clone, Display)]
 pub enum EmbeddingsModels {
     /// Ada version 002 for generating text embeddings.
+    #[strum(serialize = "text-embedding-ada-002")]
     TextEmbeddingAda002,
 
     /// Babbage version 001 for generating text embeddings.
+    #[strum(serialize = "text-embedding-babbage-001")]
     TextEmbeddingBabbage001,
 
     /// Babbage version 002 for generating text embeddings.
+    #[strum(serialize = "text-embedding-babbage-002")]
     TextEmbeddingBabbage002,
 
     /// Curie version 001 for generating text embeddings.
+    #[strum(serialize = "text-embedding-curie-001")]
     TextEmbeddingCurie001,
 
     /// Curie version 002 for generating text embeddings.
+    #[strum(serialize = "text-embedding-curie-002")]
     TextEmbeddingCurie002,
 
     /// Davinci version 001 for generating text embeddings.
+    #[strum(serialize = "text-embedding-davinci-001")]
     TextEmbeddingDavinci001,
 
     /// Davinci version 002 for generating text embeddings.
+    #[strum(serialize = "text-embedding-davinci-002")]
     TextEmbeddingDavinci002,
+
+    /// Small third-generation embedding model, supporting shortened `dimensions`.
+    #[strum(serialize = "text-embedding-3-small")]
+    TextEmbedding3Small,
+
+    /// Large third-generation embedding model, supporting shortened `dimensions`.
+    #[strum(serialize = "text-embedding-3-large")]
+    TextEmbedding3Large,
+}
+
+impl EmbeddingsModels {
+    /// Returns whether the model accepts a custom `dimensions` parameter.
+    pub fn supports_dimensions(&self) -> bool {
+        matches!(
+            self,
+            EmbeddingsModels::TextEmbedding3Small | EmbeddingsModels::TextEmbedding3Large
+        )
+    }
 }
 
 /// Enum representing various AI models.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+///
+/// Models serialize as their wire name, such as `gpt-4o`. Names that match no known model, such
+/// as fine-tuned model IDs, deserialize into `Model::Custom`.
+#[derive(Debug, Clone)]
 pub enum Model {
     /// GPT-4 models for advanced language processing.
     GPT4(GPT4),
@@ -114,6 +194,9 @@ pub enum Model {
     /// GPT-3 models for general language processing.
     GPT3(GPT3),
 
+    /// O-series models for multi-step reasoning.
+    OSeries(OSeries),
+
     /// Dalle models for image generation.
     Dalle(Dalle),
 
@@ -125,6 +208,17 @@ pub enum Model {
 
     /// Embeddings models for generating text embeddings.
     Embedding(EmbeddingsModels),
+
+    /// Any other model, such as a fine-tuned model, a model served by an OpenAI-compatible
+    /// server, or one released after this crate.
+    Custom(String),
+}
+
+impl Model {
+    /// Creates a `Model::Custom` with the given wire name, such as a fine-tuned model ID.
+    pub fn custom(name: impl Into<String>) -> Self {
+        Model::Custom(name.into())
+    }
 }
 
 impl Default for Model {
@@ -141,10 +235,131 @@ impl ToString for Model {
         match self {
             Model::GPT4(model) => model.to_string(),
             Model::GPT3(model) => model.to_string(),
+            Model::OSeries(model) => model.to_string(),
             Model::Dalle(model) => model.to_string(),
             Model::Whisper(model) => model.to_string(),
             Model::Clip(model) => model.to_string(),
             Model::Embedding(model) => model.to_string(),
+            Model::Custom(model) => model.clone(),
+        }
+    }
+}
+
+impl From<&str> for Model {
+    /// Converts a wire name into the matching model, or `Model::Custom` if none matches.
+    fn from(name: &str) -> Self {
+        if let Ok(model) = GPT4::from_str(name) {
+            Model::GPT4(model)
+        } else if let Ok(model) = GPT3::from_str(name) {
+            Model::GPT3(model)
+        } else if let Ok(model) = OSeries::from_str(name) {
+            Model::OSeries(model)
+        } else if let Ok(model) = Dalle::from_str(name) {
+            Model::Dalle(model)
+        } else if let Ok(model) = Whisper::from_str(name) {
+            Model::Whisper(model)
+        } else if let Ok(model) = ClipModels::from_str(name) {
+            Model::Clip(model)
+        } else if let Ok(model) = EmbeddingsModels::from_str(name) {
+            Model::Embedding(model)
+        } else {
+            Model::Custom(name.to_owned())
+        }
+    }
+}
+
+impl Serialize for Model {
+    /// Serializes the model as its wire name.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Model {
+    /// Deserializes a model from its wire name, falling back to `Model::Custom`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Model::from(name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_round_trip(model: Model, wire: &str) {
+        assert_eq!(
+            serde_json::to_string(&model).unwrap(),
+            format!("\"{}\"", wire)
+        );
+        let parsed: Model = serde_json::from_str(&format!("\"{}\"", wire)).unwrap();
+        assert_eq!(parsed.to_string(), wire);
+        assert!(
+            !matches!(parsed, Model::Custom(_)),
+            "{} parsed as Custom",
+            wire
+        );
+    }
+
+    #[test]
+    fn legacy_models_round_trip_through_wire_names() {
+        assert_round_trip(
+            Model::Embedding(EmbeddingsModels::TextEmbeddingAda002),
+            "text-embedding-ada-002",
+        );
+        assert_round_trip(
+            Model::Embedding(EmbeddingsModels::TextEmbeddingDavinci002),
+            "text-embedding-davinci-002",
+        );
+        assert_round_trip(Model::Whisper(Whisper::WhisperLarge), "whisper-large");
+        assert_round_trip(Model::Whisper(Whisper::WhisperTiny), "whisper-tiny");
+        assert_round_trip(
+            Model::Clip(ClipModels::ClipVitBasePatch32),
+            "clip-vit-base-patch32",
+        );
+        assert_round_trip(Model::Dalle(Dalle::Dalle2), "dall-e-2");
+    }
+
+    #[test]
+    fn known_models_round_trip_through_wire_names() {
+        assert_round_trip(Model::GPT4(GPT4::GPT4o), "gpt-4o");
+        assert_round_trip(Model::OSeries(OSeries::O3Mini), "o3-mini");
+        assert_round_trip(
+            Model::Embedding(EmbeddingsModels::TextEmbedding3Small),
+            "text-embedding-3-small",
+        );
+    }
+
+    #[test]
+    fn fine_tuned_model_ids_deserialize_as_custom() {
+        let model: Model =
+            serde_json::from_str("\"ft:gpt-4o-mini-2024-07-18:my-org::abc123\"").unwrap();
+        match &model {
+            Model::Custom(name) => {
+                assert_eq!(name, "ft:gpt-4o-mini-2024-07-18:my-org::abc123")
+            }
+            other => panic!("expected a custom model, got {:?}", other),
+        }
+        assert_eq!(
+            serde_json::to_string(&model).unwrap(),
+            "\"ft:gpt-4o-mini-2024-07-18:my-org::abc123\""
+        );
+    }
+
+    #[test]
+    fn newer_models_round_trip_through_wire_names() {
+        let cases = [
+            (Model::GPT4(GPT4::GPT4oMini), "gpt-4o-mini"),
+            (Model::GPT4(GPT4::GPT41), "gpt-4.1"),
+            (Model::GPT4(GPT4::GPT41Mini), "gpt-4.1-mini"),
+            (Model::GPT4(GPT4::GPT41Nano), "gpt-4.1-nano"),
+            (Model::OSeries(OSeries::O1), "o1"),
+            (Model::OSeries(OSeries::O1Mini), "o1-mini"),
+            (Model::OSeries(OSeries::O3), "o3"),
+            (Model::OSeries(OSeries::O4Mini), "o4-mini"),
+        ];
+        for (model, wire) in cases {
+            assert_round_trip(model, wire);
         }
     }
 }
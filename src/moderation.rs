@@ -1,6 +1,8 @@
 //! This module defines the structures and methods for creating and handling moderation requests and responses.
 //! It includes:
 //! - `CreateModerationRequest`: Struct for creating a moderation request with optional model specification.
+//! - `ModerationInput`: Enum for an input given as a text, a batch of texts, or text and image parts.
+//! - `ModerationInputItem` / `ModerationImageUrl`: Types describing a part of a multimodal input.
 //! - `CreateModerationResponse`: Struct for the response from a moderation request.
 //! - `ModerationResult`: Struct representing the result of moderation, including categories and scores.
 //! - `ModerationCategories`: Struct for categorizing the types of content flagged by moderation.
@@ -15,17 +17,20 @@ use crate::impl_builder_methods;
 /// Represents a request to create a moderation check.
 #[derive(Debug, Serialize, Clone)]
 pub struct CreateModerationRequest {
-    /// Input text to be moderated.
-    pub input: String,
+    /// Input text, batch of texts, or multimodal parts to be moderated.
+    pub input: ModerationInput,
     /// Optional model to be used for moderation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
 }
 
 impl CreateModerationRequest {
-    /// Creates a new `CreateModerationRequest` with the specified input text.
-    pub fn new(input: String) -> Self {
-        Self { input, model: None }
+    /// Creates a new `CreateModerationRequest` with the specified input.
+    pub fn new(input: impl Into<ModerationInput>) -> Self {
+        Self {
+            input: input.into(),
+            model: None,
+        }
     }
 }
 
@@ -34,6 +39,83 @@ impl_builder_methods!(
     model: String
 );
 
+/// Represents the input of a moderation request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ModerationInput {
+    /// A single text.
+    Text(String),
+    /// A batch of texts, each producing its own result.
+    Batch(Vec<String>),
+    /// Text and image parts moderated together, supported by `omni-moderation` models.
+    Multimodal(Vec<ModerationInputItem>),
+}
+
+impl From<String> for ModerationInput {
+    /// Converts a string into `ModerationInput::Text`.
+    fn from(text: String) -> Self {
+        ModerationInput::Text(text)
+    }
+}
+
+impl From<&str> for ModerationInput {
+    /// Converts a string into `ModerationInput::Text`.
+    fn from(text: &str) -> Self {
+        ModerationInput::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<String>> for ModerationInput {
+    /// Converts a list of strings into `ModerationInput::Batch`.
+    fn from(texts: Vec<String>) -> Self {
+        ModerationInput::Batch(texts)
+    }
+}
+
+impl From<Vec<ModerationInputItem>> for ModerationInput {
+    /// Converts a list of parts into `ModerationInput::Multimodal`.
+    fn from(items: Vec<ModerationInputItem>) -> Self {
+        ModerationInput::Multimodal(items)
+    }
+}
+
+/// Represents a part of a multimodal moderation input.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationInputItem {
+    /// A text part.
+    Text {
+        /// Text to be moderated.
+        text: String,
+    },
+    /// An image part.
+    ImageUrl {
+        /// URL of the image.
+        image_url: ModerationImageUrl,
+    },
+}
+
+impl ModerationInputItem {
+    /// Creates a text part.
+    pub fn text(text: impl Into<String>) -> Self {
+        ModerationInputItem::Text { text: text.into() }
+    }
+
+    /// Creates an image part from a URL or base64 data URL.
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ModerationInputItem::ImageUrl {
+            image_url: ModerationImageUrl { url: url.into() },
+        }
+    }
+}
+
+/// Represents the URL of an image in a moderation input.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ModerationImageUrl {
+    /// URL or base64 data URL of the image.
+    pub url: String,
+}
+
 /// Represents the response from a moderation check.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CreateModerationResponse {
@@ -105,3 +187,46 @@ pub struct ModerationCategoryScores {
     #[serde(rename = "violence/graphic")]
     pub violence_graphic_score: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_text_input() {
+        let req = CreateModerationRequest::new("hello");
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({ "input": "hello" })
+        );
+    }
+
+    #[test]
+    fn serializes_batch_input() {
+        let req = CreateModerationRequest::new(vec!["one".to_owned(), "two".to_owned()]);
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({ "input": ["one", "two"] })
+        );
+    }
+
+    #[test]
+    fn serializes_multimodal_input() {
+        let req = CreateModerationRequest::new(vec![
+            ModerationInputItem::text("is this ok?"),
+            ModerationInputItem::image_url("https://example.com/image.png"),
+        ])
+        .model("omni-moderation-latest".to_owned());
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({
+                "input": [
+                    { "type": "text", "text": "is this ok?" },
+                    { "type": "image_url", "image_url": { "url": "https://example.com/image.png" } }
+                ],
+                "model": "omni-moderation-latest"
+            })
+        );
+    }
+}
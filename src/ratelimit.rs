@@ -0,0 +1,156 @@
+//! This module reads the rate limit state the API reports on every response.
+//! It includes:
+//! - `RateLimitInfo`: Struct holding the request and token limits, what remains of them, and
+//!   when they reset, parsed from the `x-ratelimit-*` headers.
+
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Represents the rate limit state reported by the `x-ratelimit-*` response headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Maximum number of requests allowed before the limit resets.
+    pub limit_requests: Option<u64>,
+    /// Maximum number of tokens allowed before the limit resets.
+    pub limit_tokens: Option<u64>,
+    /// Number of requests remaining before the limit is hit.
+    pub remaining_requests: Option<u64>,
+    /// Number of tokens remaining before the limit is hit.
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request limit resets.
+    pub reset_requests: Option<Duration>,
+    /// Time until the token limit resets.
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parses the rate limit state from response headers, returning `None` if none are present.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let count = |name: &str| header(name).and_then(|value| value.parse().ok());
+        let reset = |name: &str| header(name).and_then(parse_reset);
+
+        let info = Self {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        };
+        if info == Self::default() {
+            None
+        } else {
+            Some(info)
+        }
+    }
+
+    /// Returns how long to wait before the next request, once `elapsed` has passed since these
+    /// headers were received, or `None` if quota remains.
+    pub fn wait_time(&self, elapsed: Duration) -> Option<Duration> {
+        let exhausted = |remaining: Option<u64>, reset: Option<Duration>| {
+            reset.filter(|_| remaining == Some(0))
+        };
+        let wait = exhausted(self.remaining_requests, self.reset_requests)
+            .max(exhausted(self.remaining_tokens, self.reset_tokens))?;
+        wait.checked_sub(elapsed).filter(|wait| !wait.is_zero())
+    }
+}
+
+/// Parses a reset duration such as `20ms`, `1s`, `6m0s` or `1h2m3.5s`.
+fn parse_reset(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let amount: f64 = rest[..split].parse().ok()?;
+        rest = &rest[split..];
+        let (seconds, unit_len) = if rest.starts_with("ms") {
+            (0.001, 2)
+        } else if rest.starts_with('h') {
+            (3600.0, 1)
+        } else if rest.starts_with('m') {
+            (60.0, 1)
+        } else if rest.starts_with('s') {
+            (1.0, 1)
+        } else {
+            return None;
+        };
+        total += amount * seconds;
+        rest = &rest[unit_len..];
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn parses_reset_durations() {
+        assert_eq!(parse_reset("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_reset("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_reset("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(
+            parse_reset("1h2m3.5s"),
+            Some(Duration::from_secs_f64(3723.5))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_reset_durations() {
+        assert_eq!(parse_reset("soon"), None);
+        assert_eq!(parse_reset("5x"), None);
+        assert_eq!(parse_reset("1.2.3s"), None);
+        assert_eq!(parse_reset("99999999999999999999999999h"), None);
+    }
+
+    #[test]
+    fn reads_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimit-limit-requests", "60"),
+            ("x-ratelimit-limit-tokens", "150000"),
+            ("x-ratelimit-remaining-requests", "59"),
+            ("x-ratelimit-remaining-tokens", "149984"),
+            ("x-ratelimit-reset-requests", "1s"),
+            ("x-ratelimit-reset-tokens", "6m0s"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers),
+            Some(RateLimitInfo {
+                limit_requests: Some(60),
+                limit_tokens: Some(150000),
+                remaining_requests: Some(59),
+                remaining_tokens: Some(149984),
+                reset_requests: Some(Duration::from_secs(1)),
+                reset_tokens: Some(Duration::from_secs(360)),
+            })
+        );
+    }
+
+    #[test]
+    fn skips_missing_and_malformed_headers() {
+        assert_eq!(RateLimitInfo::from_headers(&HeaderMap::new()), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("0"),
+        );
+        headers.insert("x-ratelimit-limit-tokens", HeaderValue::from_static("lots"));
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("later"),
+        );
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.remaining_requests, Some(0));
+        assert_eq!(info.limit_tokens, None);
+        assert_eq!(info.reset_requests, None);
+    }
+}
@@ -0,0 +1,453 @@
+//! This module defines the structures for the Responses API, which unifies chat, tool use and
+//! conversation state behind a single endpoint.
+//! It includes:
+//! - `CreateResponseRequest`: Struct for creating a model response.
+//! - `ResponseInput`: Enum for an input given as plain text or a list of input items.
+//! - `ResponseInputItem` / `ResponseInputContent` / `ResponseInputPart`: Types describing input messages and tool outputs.
+//! - `ResponseTool`: Enum for the tools a response may call, built from a chat `Tool` for functions.
+//! - `ResponseReasoning`: Struct configuring reasoning models.
+//! - `ResponseObject`: Struct representing a model response and its output.
+//! - `ResponseOutputItem` / `ResponseOutputContent`: Enums describing the items a response produced.
+//! - `ResponseUsage`: Struct for tracking token usage of a response.
+//! - `impl_builder_methods!`: Macro for generating builder methods for structs.
+
+use crate::{
+    chat_completion::{Function, ReasoningEffort, Tool},
+    common::{MessageRole, ObjectType},
+    impl_builder_methods,
+    models::Model,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Represents a request to create a model response.
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateResponseRequest {
+    /// Model used to generate the response.
+    pub model: Model,
+    /// Text or items the model responds to.
+    pub input: ResponseInput,
+    /// Optional system instructions inserted ahead of the input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Optional tools the model may call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ResponseTool>>,
+    /// Optional ID of a previous response whose conversation this one continues.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+    /// Optional sampling temperature, from 0 to 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Optional nucleus sampling probability mass, from 0 to 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Optional maximum number of output tokens, including reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i64>,
+    /// Whether the model may call several tools in one turn.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    /// Optional reasoning configuration for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ResponseReasoning>,
+    /// Whether to store the response so it can be retrieved or continued later.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    /// Optional key-value pairs attached to the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Optional user identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+impl CreateResponseRequest {
+    /// Creates a new `CreateResponseRequest` with the specified model and input.
+    pub fn new(model: Model, input: impl Into<ResponseInput>) -> Self {
+        Self {
+            model,
+            input: input.into(),
+            instructions: None,
+            tools: None,
+            previous_response_id: None,
+            temperature: None,
+            top_p: None,
+            max_output_tokens: None,
+            parallel_tool_calls: None,
+            reasoning: None,
+            store: None,
+            metadata: None,
+            user: None,
+        }
+    }
+}
+
+impl_builder_methods!(
+    CreateResponseRequest,
+    instructions: String,
+    tools: Vec<ResponseTool>,
+    previous_response_id: String,
+    temperature: f64,
+    top_p: f64,
+    max_output_tokens: i64,
+    parallel_tool_calls: bool,
+    reasoning: ResponseReasoning,
+    store: bool,
+    metadata: HashMap<String, String>,
+    user: String
+);
+
+/// Represents the input of a response request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseInput {
+    /// A single user message given as text.
+    Text(String),
+    /// A list of input messages and tool outputs.
+    Items(Vec<ResponseInputItem>),
+}
+
+impl From<String> for ResponseInput {
+    /// Converts a string into `ResponseInput::Text`.
+    fn from(text: String) -> Self {
+        ResponseInput::Text(text)
+    }
+}
+
+impl From<&str> for ResponseInput {
+    /// Converts a string into `ResponseInput::Text`.
+    fn from(text: &str) -> Self {
+        ResponseInput::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<ResponseInputItem>> for ResponseInput {
+    /// Converts a list of items into `ResponseInput::Items`.
+    fn from(items: Vec<ResponseInputItem>) -> Self {
+        ResponseInput::Items(items)
+    }
+}
+
+/// Represents an item of a response input.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseInputItem {
+    /// A message from the user, system or developer, or an earlier assistant turn.
+    Message {
+        /// Role of the message author.
+        role: MessageRole,
+        /// Content of the message.
+        content: ResponseInputContent,
+    },
+    /// The output of a function call requested by an earlier response.
+    FunctionCallOutput {
+        /// ID of the function call this output answers.
+        call_id: String,
+        /// Output of the function, typically JSON.
+        output: String,
+    },
+}
+
+impl ResponseInputItem {
+    /// Creates a message item with the given role and text.
+    pub fn message(role: MessageRole, text: impl Into<String>) -> Self {
+        ResponseInputItem::Message {
+            role,
+            content: ResponseInputContent::Text(text.into()),
+        }
+    }
+
+    /// Creates an item carrying the output of the given function call.
+    pub fn function_call_output(call_id: String, output: String) -> Self {
+        ResponseInputItem::FunctionCallOutput { call_id, output }
+    }
+}
+
+/// Represents the content of an input message.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseInputContent {
+    /// Plain text content.
+    Text(String),
+    /// A list of text, image and file parts.
+    Parts(Vec<ResponseInputPart>),
+}
+
+/// Represents a part of an input message's content.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseInputPart {
+    /// A text part.
+    InputText {
+        /// Text of the part.
+        text: String,
+    },
+    /// An image given by URL, data URL or uploaded file.
+    InputImage {
+        /// Optional URL or base64 data URL of the image.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        image_url: Option<String>,
+        /// Optional ID of an uploaded image file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        /// Optional level of detail, `low`, `high` or `auto`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+    /// An uploaded file.
+    InputFile {
+        /// ID of the uploaded file.
+        file_id: String,
+    },
+}
+
+/// Represents a tool a response may call.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTool {
+    /// A function defined by the caller.
+    Function(Function),
+    /// The built-in web search tool.
+    WebSearchPreview,
+    /// The built-in file search tool over the given vector stores.
+    FileSearch {
+        /// IDs of the vector stores searched.
+        vector_store_ids: Vec<String>,
+    },
+}
+
+impl From<Tool> for ResponseTool {
+    /// Converts a chat completion function tool into `ResponseTool::Function`.
+    fn from(tool: Tool) -> Self {
+        ResponseTool::Function(tool.function)
+    }
+}
+
+/// Represents the reasoning configuration of a response request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResponseReasoning {
+    /// Optional effort the model spends reasoning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+}
+
+/// Represents a model response.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResponseObject {
+    /// Unique identifier for the response.
+    pub id: String,
+    /// Object type, typically `ObjectType::Response`.
+    pub object: ObjectType,
+    /// Timestamp of when the response was created.
+    pub created_at: i64,
+    /// Status of the response, such as `completed`, `in_progress` or `incomplete`.
+    pub status: String,
+    /// Model used to generate the response.
+    pub model: String,
+    /// Items produced by the model.
+    #[serde(default)]
+    pub output: Vec<ResponseOutputItem>,
+    /// Optional instructions the response was created with.
+    #[serde(default)]
+    pub instructions: Option<String>,
+    /// Optional ID of the response this one continues.
+    #[serde(default)]
+    pub previous_response_id: Option<String>,
+    /// Optional error, set when the response failed.
+    #[serde(default)]
+    pub error: Option<Value>,
+    /// Optional details on why the response is incomplete.
+    #[serde(default)]
+    pub incomplete_details: Option<Value>,
+    /// Optional token usage of the response.
+    #[serde(default)]
+    pub usage: Option<ResponseUsage>,
+    /// Metadata associated with the response.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Optional headers from the response.
+    pub headers: Option<HashMap<String, String>>,
+}
+
+impl ResponseObject {
+    /// Returns the text of every `output_text` part of the output messages, concatenated.
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                ResponseOutputItem::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|content| match content {
+                ResponseOutputContent::OutputText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Represents an item produced by a response.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseOutputItem {
+    /// A message from the model.
+    Message {
+        /// Unique identifier for the item.
+        id: String,
+        /// Role of the message author, always `assistant`.
+        role: MessageRole,
+        /// Content of the message.
+        content: Vec<ResponseOutputContent>,
+    },
+    /// A call of a function tool.
+    FunctionCall {
+        /// Optional unique identifier for the item.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        /// ID used to send the function's output back with `function_call_output`.
+        call_id: String,
+        /// Name of the function called.
+        name: String,
+        /// JSON arguments of the call.
+        arguments: String,
+    },
+    /// An item type not known to this library, such as a built-in tool call.
+    #[serde(other)]
+    Other,
+}
+
+/// Represents a part of an output message's content.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseOutputContent {
+    /// Text generated by the model.
+    OutputText {
+        /// Generated text.
+        text: String,
+        /// Annotations such as citations.
+        #[serde(default)]
+        annotations: Vec<Value>,
+    },
+    /// A refusal to answer.
+    Refusal {
+        /// Explanation of the refusal.
+        refusal: String,
+    },
+    /// A content type not known to this library.
+    #[serde(other)]
+    Other,
+}
+
+/// Represents token usage of a response.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ResponseUsage {
+    /// Number of tokens in the input.
+    pub input_tokens: i64,
+    /// Number of tokens in the output, including reasoning tokens.
+    pub output_tokens: i64,
+    /// Total number of tokens used.
+    pub total_tokens: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_completion::{FunctionParameters, JSONSchemaType, ToolType};
+    use serde_json::json;
+
+    #[test]
+    fn serializes_create_response_request() {
+        let tool = Tool {
+            r#type: ToolType::Function,
+            function: Function {
+                name: "get_weather".to_owned(),
+                description: None,
+                parameters: FunctionParameters {
+                    schema_type: JSONSchemaType::Object,
+                    properties: None,
+                    required: None,
+                },
+            },
+        };
+        let req = CreateResponseRequest::new(
+            Model::custom("gpt-4o"),
+            vec![
+                ResponseInputItem::message(MessageRole::User, "Weather in Paris?"),
+                ResponseInputItem::function_call_output(
+                    "call_abc123".to_owned(),
+                    "{\"temp\":21}".to_owned(),
+                ),
+            ],
+        )
+        .instructions("Be brief.".to_owned())
+        .tools(vec![tool.into(), ResponseTool::WebSearchPreview]);
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({
+                "model": "gpt-4o",
+                "input": [
+                    { "type": "message", "role": "user", "content": "Weather in Paris?" },
+                    {
+                        "type": "function_call_output",
+                        "call_id": "call_abc123",
+                        "output": "{\"temp\":21}"
+                    }
+                ],
+                "instructions": "Be brief.",
+                "tools": [
+                    {
+                        "type": "function",
+                        "name": "get_weather",
+                        "parameters": { "type": "object" }
+                    },
+                    { "type": "web_search_preview" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_response_object() {
+        let response: ResponseObject = serde_json::from_value(json!({
+            "id": "resp_abc123",
+            "object": "response",
+            "created_at": 1741476542,
+            "status": "completed",
+            "model": "gpt-4o-2024-08-06",
+            "output": [
+                { "type": "web_search_call", "id": "ws_abc123", "status": "completed" },
+                {
+                    "type": "message",
+                    "id": "msg_abc123",
+                    "status": "completed",
+                    "role": "assistant",
+                    "content": [
+                        { "type": "output_text", "text": "It is 21°C", "annotations": [] },
+                        { "type": "output_text", "text": " in Paris." }
+                    ]
+                },
+                {
+                    "type": "function_call",
+                    "id": "fc_abc123",
+                    "call_id": "call_abc123",
+                    "name": "get_weather",
+                    "arguments": "{\"city\":\"Paris\"}"
+                }
+            ],
+            "usage": { "input_tokens": 36, "output_tokens": 87, "total_tokens": 123 },
+            "metadata": {}
+        }))
+        .unwrap();
+        assert_eq!(response.object, ObjectType::Response);
+        assert_eq!(response.output_text(), "It is 21°C in Paris.");
+        assert!(matches!(response.output[0], ResponseOutputItem::Other));
+        assert!(matches!(
+            &response.output[2],
+            ResponseOutputItem::FunctionCall { call_id, .. } if call_id == "call_abc123"
+        ));
+        assert_eq!(response.usage.unwrap().total_tokens, 123);
+    }
+}
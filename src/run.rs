@@ -2,19 +2,33 @@
 //! as well as handling run-related requests and responses.
 //! It includes:
 //! - `CreateRunRequest`: Struct for creating a new run with optional parameters.
+//! - `TruncationStrategy`: Struct controlling how a thread is truncated to fit a run's context window.
 //! - `ModifyRunRequest`: Struct for modifying an existing run's metadata.
 //! - `RunObject`: Struct representing a run object with various attributes.
 //! - `ListRun`: Struct for listing multiple runs.
+//! - `RequiredAction` / `SubmitToolOutputs`: Structs describing the tool calls a run waits on.
+//! - `LastError`: Struct describing the error that made a run or run step fail.
+//! - `IncompleteDetails`: Struct explaining why a run ended incomplete.
+//! - `RunPollBackoff`: Struct computing the adaptive delays between polls of a run.
 //! - `CreateThreadAndRunRequest`: Struct for creating a thread and a run simultaneously.
 //! - `RunStepObject`: Struct representing a step within a run.
 //! - `ListRunStep`: Struct for listing multiple run steps.
+//! - `RunStreamEvent`: Enum for the server-sent events emitted while a run is streamed.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
-use super::thread::CreateThreadRequest;
+use super::{
+    message::{MessageDeltaObject, MessageObject},
+    thread::{CreateThreadRequest, Message, ThreadObject},
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::{collections::HashMap, time::Duration};
 
-use crate::impl_builder_methods;
+use crate::{
+    chat_completion::ToolCall,
+    common::{ObjectType, Usage},
+    impl_builder_methods,
+};
 
 /// Represents a request to create a new run.
 #[derive(Debug, Serialize, Clone)]
@@ -33,6 +47,33 @@ pub struct CreateRunRequest {
     /// Optional metadata for the run.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+    /// Optional instructions appended to the assistant's instructions for this run only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_instructions: Option<String>,
+    /// Optional messages added to the thread before the run starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_messages: Option<Vec<Message>>,
+    /// Optional sampling temperature for the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Optional nucleus sampling parameter for the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Optional maximum number of prompt tokens used over the course of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_prompt_tokens: Option<i64>,
+    /// Optional maximum number of completion tokens used over the course of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<i64>,
+    /// Optional strategy for truncating the thread before the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation_strategy: Option<TruncationStrategy>,
+    /// Optional format the model must output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    /// Whether to stream the run as server-sent events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
 }
 
 impl CreateRunRequest {
@@ -44,6 +85,15 @@ impl CreateRunRequest {
             instructions: None,
             tools: None,
             metadata: None,
+            additional_instructions: None,
+            additional_messages: None,
+            temperature: None,
+            top_p: None,
+            max_prompt_tokens: None,
+            max_completion_tokens: None,
+            truncation_strategy: None,
+            response_format: None,
+            stream: None,
         }
     }
 }
@@ -53,9 +103,46 @@ impl_builder_methods!(
     model: String,
     instructions: String,
     tools: Vec<HashMap<String, String>>,
-    metadata: HashMap<String, String>
+    metadata: HashMap<String, String>,
+    additional_instructions: String,
+    additional_messages: Vec<Message>,
+    temperature: f64,
+    top_p: f64,
+    max_prompt_tokens: i64,
+    max_completion_tokens: i64,
+    truncation_strategy: TruncationStrategy,
+    response_format: Value,
+    stream: bool
 );
 
+/// Represents how a thread is truncated to fit the context window of a run.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct TruncationStrategy {
+    /// Type of truncation, `auto` or `last_messages`.
+    pub r#type: String,
+    /// Optional number of most recent messages kept, for the `last_messages` type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_messages: Option<i64>,
+}
+
+impl TruncationStrategy {
+    /// Creates a `TruncationStrategy` letting the API drop messages in the middle of the thread.
+    pub fn auto() -> Self {
+        Self {
+            r#type: "auto".to_owned(),
+            last_messages: None,
+        }
+    }
+
+    /// Creates a `TruncationStrategy` keeping only the given number of most recent messages.
+    pub fn last_messages(count: i64) -> Self {
+        Self {
+            r#type: "last_messages".to_owned(),
+            last_messages: Some(count),
+        }
+    }
+}
+
 /// Represents a request to modify an existing run's metadata.
 #[derive(Debug, Serialize, Clone)]
 pub struct ModifyRunRequest {
@@ -88,8 +175,8 @@ impl_builder_methods!(
 pub struct RunObject {
     /// Unique identifier for the run.
     pub id: String,
-    /// Object type, typically "run".
-    pub object: String,
+    /// Object type, typically `ObjectType::ThreadRun`.
+    pub object: ObjectType,
     /// Timestamp of when the run was created.
     pub created_at: i64,
     /// Identifier for the associated thread.
@@ -98,12 +185,12 @@ pub struct RunObject {
     pub assistant_id: String,
     /// Status of the run.
     pub status: String,
-    /// Optional required actions for the run.
+    /// Optional action needed to continue the run, set while it is `requires_action`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub required_action: Option<HashMap<String, String>>,
+    pub required_action: Option<RequiredAction>,
     /// Optional last error encountered during the run.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_error: Option<String>,
+    pub last_error: Option<LastError>,
     /// Optional expiration timestamp of the run.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<i64>,
@@ -124,15 +211,107 @@ pub struct RunObject {
     /// Optional instructions for the run.
     pub instructions: Option<String>,
     /// Tools used during the run.
-    pub tools: Vec<HashMap<String, String>>,
-    /// File IDs associated with the run.
+    pub tools: Vec<Value>,
+    /// File IDs associated with the run, absent from `assistants=v2` responses.
+    #[serde(default)]
     pub file_ids: Vec<String>,
     /// Metadata for the run.
     pub metadata: HashMap<String, String>,
+    /// Optional details on why the run ended with the `incomplete` status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incomplete_details: Option<IncompleteDetails>,
+    /// Optional token usage of the run, set once it reaches a terminal status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
 
+/// Represents the action a run waits on before it can continue.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RequiredAction {
+    /// Type of the action, currently always `submit_tool_outputs`.
+    pub r#type: String,
+    /// Tool calls whose outputs must be submitted.
+    pub submit_tool_outputs: SubmitToolOutputs,
+}
+
+/// Represents the tool calls whose outputs a run needs to continue.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SubmitToolOutputs {
+    /// Tool calls made by the run.
+    pub tool_calls: Vec<ToolCall>,
+}
+
+/// Represents the error that made a run or run step fail.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LastError {
+    /// Error code, such as `server_error` or `rate_limit_exceeded`.
+    pub code: String,
+    /// Description of the error.
+    pub message: String,
+}
+
+/// Represents why a run ended with the `incomplete` status.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct IncompleteDetails {
+    /// Reason the run ended, such as `max_completion_tokens` or `max_prompt_tokens`.
+    pub reason: String,
+}
+
+impl RunObject {
+    /// Returns whether the run stopped progressing, either because it ended or because it
+    /// needs tool outputs to continue.
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.status.as_str(),
+            "requires_action"
+                | "completed"
+                | "failed"
+                | "cancelled"
+                | "expired"
+                | "incomplete"
+        )
+    }
+}
+
+/// Computes the delays between polls of a run, doubling them while the status stays the same
+/// and going back to the shortest delay whenever it changes.
+#[derive(Debug, Clone)]
+pub struct RunPollBackoff {
+    /// Shortest delay between polls.
+    min_interval: Duration,
+    /// Longest delay between polls.
+    max_interval: Duration,
+    /// Delay returned by the next poll with an unchanged status.
+    current: Duration,
+    /// Status seen on the previous poll.
+    last_status: Option<String>,
+}
+
+impl RunPollBackoff {
+    /// Creates a new `RunPollBackoff` with delays between `min_interval` and `max_interval`.
+    pub fn new(min_interval: Duration, max_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            max_interval: max_interval.max(min_interval),
+            current: min_interval,
+            last_status: None,
+        }
+    }
+
+    /// Returns how long to wait before the next poll, given the status just observed.
+    pub fn next_delay(&mut self, status: &str) -> Duration {
+        if self.last_status.as_deref() != Some(status) {
+            self.last_status = Some(status.to_owned());
+            self.current = self.min_interval;
+        }
+        let delay = self.current;
+        self.current = (self.current * 2).min(self.max_interval);
+        delay
+    }
+}
+
 /// Represents a list of runs.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ListRun {
@@ -170,15 +349,73 @@ pub struct CreateThreadAndRunRequest {
     /// Optional metadata for the run.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+    /// Optional tool choice, such as `"required"` or a specific function to force.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<Value>,
+    /// Optional format the model must output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    /// Optional sampling temperature for the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Optional nucleus sampling parameter for the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Optional maximum number of prompt tokens used over the course of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_prompt_tokens: Option<i64>,
+    /// Optional maximum number of completion tokens used over the course of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<i64>,
+    /// Optional strategy for truncating the thread before the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation_strategy: Option<TruncationStrategy>,
+}
+
+impl CreateThreadAndRunRequest {
+    /// Creates a new `CreateThreadAndRunRequest` with the specified assistant ID.
+    pub fn new(assistant_id: String) -> Self {
+        Self {
+            assistant_id,
+            thread: None,
+            model: None,
+            instructions: None,
+            tools: None,
+            metadata: None,
+            tool_choice: None,
+            response_format: None,
+            temperature: None,
+            top_p: None,
+            max_prompt_tokens: None,
+            max_completion_tokens: None,
+            truncation_strategy: None,
+        }
+    }
 }
 
+impl_builder_methods!(
+    CreateThreadAndRunRequest,
+    thread: CreateThreadRequest,
+    model: String,
+    instructions: String,
+    tools: Vec<HashMap<String, String>>,
+    metadata: HashMap<String, String>,
+    tool_choice: Value,
+    response_format: Value,
+    temperature: f64,
+    top_p: f64,
+    max_prompt_tokens: i64,
+    max_completion_tokens: i64,
+    truncation_strategy: TruncationStrategy
+);
+
 /// Represents a step within a run.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RunStepObject {
     /// Unique identifier for the run step.
     pub id: String,
-    /// Object type, typically "run_step".
-    pub object: String,
+    /// Object type, typically `ObjectType::ThreadRunStep`.
+    pub object: ObjectType,
     /// Timestamp of when the run step was created.
     pub created_at: i64,
     /// Identifier for the assistant.
@@ -192,11 +429,11 @@ pub struct RunStepObject {
     pub run_step_type: String,
     /// Status of the run step.
     pub status: String,
-    /// Details about the run step.
-    pub step_details: HashMap<String, String>,
+    /// Details about the run step, such as the message it created or the tool calls it made.
+    pub step_details: Value,
     /// Optional last error encountered during the run step.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_error: Option<String>,
+    pub last_error: Option<LastError>,
     /// Optional expiration timestamp of the run step.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<i64>,
@@ -234,3 +471,372 @@ pub struct ListRunStep {
     /// Optional headers from the response.
     pub headers: Option<HashMap<String, String>>,
 }
+
+/// Represents an event emitted while a run is streamed.
+#[derive(Debug)]
+pub enum RunStreamEvent {
+    /// A thread was created (`thread.created`).
+    ThreadCreated(ThreadObject),
+    /// A run was created (`thread.run.created`).
+    RunCreated(RunObject),
+    /// A run moved to `queued` (`thread.run.queued`).
+    RunQueued(RunObject),
+    /// A run moved to `in_progress` (`thread.run.in_progress`).
+    RunInProgress(RunObject),
+    /// A run needs tool outputs to continue (`thread.run.requires_action`).
+    RunRequiresAction(RunObject),
+    /// A run completed (`thread.run.completed`).
+    RunCompleted(RunObject),
+    /// A run ended before completing, such as on a token limit (`thread.run.incomplete`).
+    RunIncomplete(RunObject),
+    /// A run failed (`thread.run.failed`).
+    RunFailed(RunObject),
+    /// A run moved to `cancelling` (`thread.run.cancelling`).
+    RunCancelling(RunObject),
+    /// A run was cancelled (`thread.run.cancelled`).
+    RunCancelled(RunObject),
+    /// A run expired (`thread.run.expired`).
+    RunExpired(RunObject),
+    /// A run step was created (`thread.run.step.created`).
+    StepCreated(RunStepObject),
+    /// A run step moved to `in_progress` (`thread.run.step.in_progress`).
+    StepInProgress(RunStepObject),
+    /// Parts of a run step changed (`thread.run.step.delta`).
+    StepDelta(Value),
+    /// A run step completed (`thread.run.step.completed`).
+    StepCompleted(RunStepObject),
+    /// A run step failed (`thread.run.step.failed`).
+    StepFailed(RunStepObject),
+    /// A message was created (`thread.message.created`).
+    MessageCreated(MessageObject),
+    /// A message moved to `in_progress` (`thread.message.in_progress`).
+    MessageInProgress(MessageObject),
+    /// Parts of a message changed (`thread.message.delta`).
+    MessageDelta(MessageDeltaObject),
+    /// A message completed (`thread.message.completed`).
+    MessageCompleted(MessageObject),
+    /// The stream reported an error (`error`).
+    Error(Value),
+    /// An event not known to this library, kept with its raw payload.
+    Unknown {
+        /// Name of the event.
+        event: String,
+        /// Raw payload of the event.
+        data: Value,
+    },
+}
+
+impl RunStreamEvent {
+    /// Parses an event from its SSE event name and JSON payload.
+    pub fn parse(event: &str, data: &str) -> Result<Self, serde_json::Error> {
+        Ok(match event {
+            "thread.created" => {
+                RunStreamEvent::ThreadCreated(serde_json::from_str(data)?)
+            }
+            "thread.run.created" => {
+                RunStreamEvent::RunCreated(serde_json::from_str(data)?)
+            }
+            "thread.run.queued" => RunStreamEvent::RunQueued(serde_json::from_str(data)?),
+            "thread.run.in_progress" => {
+                RunStreamEvent::RunInProgress(serde_json::from_str(data)?)
+            }
+            "thread.run.requires_action" => {
+                RunStreamEvent::RunRequiresAction(serde_json::from_str(data)?)
+            }
+            "thread.run.completed" => {
+                RunStreamEvent::RunCompleted(serde_json::from_str(data)?)
+            }
+            "thread.run.incomplete" => {
+                RunStreamEvent::RunIncomplete(serde_json::from_str(data)?)
+            }
+            "thread.run.failed" => RunStreamEvent::RunFailed(serde_json::from_str(data)?),
+            "thread.run.cancelling" => {
+                RunStreamEvent::RunCancelling(serde_json::from_str(data)?)
+            }
+            "thread.run.cancelled" => {
+                RunStreamEvent::RunCancelled(serde_json::from_str(data)?)
+            }
+            "thread.run.expired" => {
+                RunStreamEvent::RunExpired(serde_json::from_str(data)?)
+            }
+            "thread.run.step.created" => {
+                RunStreamEvent::StepCreated(serde_json::from_str(data)?)
+            }
+            "thread.run.step.in_progress" => {
+                RunStreamEvent::StepInProgress(serde_json::from_str(data)?)
+            }
+            "thread.run.step.delta" => {
+                RunStreamEvent::StepDelta(serde_json::from_str(data)?)
+            }
+            "thread.run.step.completed" => {
+                RunStreamEvent::StepCompleted(serde_json::from_str(data)?)
+            }
+            "thread.run.step.failed" => {
+                RunStreamEvent::StepFailed(serde_json::from_str(data)?)
+            }
+            "thread.message.created" => {
+                RunStreamEvent::MessageCreated(serde_json::from_str(data)?)
+            }
+            "thread.message.in_progress" => {
+                RunStreamEvent::MessageInProgress(serde_json::from_str(data)?)
+            }
+            "thread.message.delta" => {
+                RunStreamEvent::MessageDelta(serde_json::from_str(data)?)
+            }
+            "thread.message.completed" => {
+                RunStreamEvent::MessageCompleted(serde_json::from_str(data)?)
+            }
+            "error" => RunStreamEvent::Error(serde_json::from_str(data)?),
+            _ => RunStreamEvent::Unknown {
+                event: event.to_owned(),
+                data: serde_json::from_str(data)?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_json(status: &str, extra: &str) -> String {
+        format!(
+            r#"{{
+                "id": "run_abc123",
+                "object": "thread.run",
+                "created_at": 1699063290,
+                "assistant_id": "asst_abc123",
+                "thread_id": "thread_abc123",
+                "status": "{}",
+                "started_at": 1699063290,
+                "expires_at": null,
+                "cancelled_at": null,
+                "failed_at": null,
+                "completed_at": null,
+                "model": "gpt-4o",
+                "instructions": null,
+                "tools": [{{"type": "function", "function": {{"name": "get_weather", "parameters": {{"type": "object"}}}}}}],
+                "metadata": {{}},
+                "tool_choice": "auto",
+                "parallel_tool_calls": true{}
+            }}"#,
+            status, extra
+        )
+    }
+
+    #[test]
+    fn deserializes_run_requiring_action() {
+        let run: RunObject = serde_json::from_str(&run_json(
+            "requires_action",
+            r#", "required_action": {
+                "type": "submit_tool_outputs",
+                "submit_tool_outputs": {
+                    "tool_calls": [{
+                        "id": "call_abc123",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                    }]
+                }
+            }"#,
+        ))
+        .unwrap();
+        let action = run.required_action.unwrap();
+        assert_eq!(action.r#type, "submit_tool_outputs");
+        let call = &action.submit_tool_outputs.tool_calls[0];
+        assert_eq!(call.id, "call_abc123");
+        assert_eq!(call.function.name.as_deref(), Some("get_weather"));
+        assert!(run.file_ids.is_empty());
+    }
+
+    #[test]
+    fn deserializes_failed_run() {
+        let run: RunObject = serde_json::from_str(&run_json(
+            "failed",
+            r#", "last_error": {"code": "rate_limit_exceeded", "message": "Slow down"}"#,
+        ))
+        .unwrap();
+        let error = run.last_error.unwrap();
+        assert_eq!(error.code, "rate_limit_exceeded");
+        assert_eq!(error.message, "Slow down");
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let mut backoff =
+            RunPollBackoff::new(Duration::from_millis(100), Duration::from_millis(500));
+        let delays: Vec<u64> = (0..5)
+            .map(|_| backoff.next_delay("in_progress").as_millis() as u64)
+            .collect();
+        assert_eq!(delays, [100, 200, 400, 500, 500]);
+    }
+
+    #[test]
+    fn backoff_resets_when_status_changes() {
+        let mut backoff =
+            RunPollBackoff::new(Duration::from_millis(100), Duration::from_millis(500));
+        assert_eq!(backoff.next_delay("queued"), Duration::from_millis(100));
+        assert_eq!(backoff.next_delay("queued"), Duration::from_millis(200));
+        assert_eq!(
+            backoff.next_delay("in_progress"),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            backoff.next_delay("in_progress"),
+            Duration::from_millis(200)
+        );
+    }
+
+    #[test]
+    fn parses_settled_run_events() {
+        let requires_action = run_json(
+            "requires_action",
+            r#", "required_action": {
+                "type": "submit_tool_outputs",
+                "submit_tool_outputs": {"tool_calls": []}
+            }"#,
+        );
+        assert!(matches!(
+            RunStreamEvent::parse("thread.run.requires_action", &requires_action)
+                .unwrap(),
+            RunStreamEvent::RunRequiresAction(_)
+        ));
+        let failed = run_json(
+            "failed",
+            r#", "last_error": {"code": "server_error", "message": "Boom"}"#,
+        );
+        assert!(matches!(
+            RunStreamEvent::parse("thread.run.failed", &failed).unwrap(),
+            RunStreamEvent::RunFailed(_)
+        ));
+        let incomplete = run_json(
+            "incomplete",
+            r#", "incomplete_details": {"reason": "max_completion_tokens"}"#,
+        );
+        assert!(matches!(
+            RunStreamEvent::parse("thread.run.incomplete", &incomplete).unwrap(),
+            RunStreamEvent::RunIncomplete(_)
+        ));
+        assert!(matches!(
+            RunStreamEvent::parse("thread.run.cancelling", &run_json("cancelling", ""))
+                .unwrap(),
+            RunStreamEvent::RunCancelling(_)
+        ));
+    }
+
+    #[test]
+    fn parses_tool_call_step_event() {
+        let step = r#"{
+            "id": "step_abc123",
+            "object": "thread.run.step",
+            "created_at": 1699063291,
+            "run_id": "run_abc123",
+            "assistant_id": "asst_abc123",
+            "thread_id": "thread_abc123",
+            "type": "tool_calls",
+            "status": "failed",
+            "step_details": {
+                "type": "tool_calls",
+                "tool_calls": [{
+                    "id": "call_abc123",
+                    "type": "code_interpreter",
+                    "code_interpreter": {"input": "", "outputs": []}
+                }]
+            },
+            "last_error": {"code": "server_error", "message": "Boom"},
+            "expired_at": null,
+            "cancelled_at": null,
+            "failed_at": 1699063292,
+            "completed_at": null,
+            "metadata": {}
+        }"#;
+        match RunStreamEvent::parse("thread.run.step.failed", step).unwrap() {
+            RunStreamEvent::StepFailed(step) => {
+                assert_eq!(step.step_details["type"], "tool_calls");
+                assert_eq!(step.last_error.unwrap().code, "server_error");
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserializes_completed_run_with_usage() {
+        let run: RunObject = serde_json::from_str(&run_json(
+            "completed",
+            r#", "incomplete_details": null,
+            "usage": {"prompt_tokens": 123, "completion_tokens": 456, "total_tokens": 579}"#,
+        ))
+        .unwrap();
+        let usage = run.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 123);
+        assert_eq!(usage.completion_tokens, 456);
+        assert_eq!(usage.total_tokens, 579);
+        assert!(run.incomplete_details.is_none());
+    }
+
+    #[test]
+    fn deserializes_incomplete_run_details() {
+        let run: RunObject = serde_json::from_str(&run_json(
+            "incomplete",
+            r#", "incomplete_details": {"reason": "max_completion_tokens"}, "usage": null"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            run.incomplete_details.unwrap().reason,
+            "max_completion_tokens"
+        );
+        assert!(run.usage.is_none());
+    }
+
+    #[test]
+    fn serializes_truncation_strategy_and_token_limits() {
+        let req = CreateRunRequest::new("asst_abc123".to_owned())
+            .max_prompt_tokens(2000)
+            .max_completion_tokens(500)
+            .truncation_strategy(TruncationStrategy::last_messages(10));
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "assistant_id": "asst_abc123",
+                "max_prompt_tokens": 2000,
+                "max_completion_tokens": 500,
+                "truncation_strategy": { "type": "last_messages", "last_messages": 10 }
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(TruncationStrategy::auto()).unwrap(),
+            serde_json::json!({ "type": "auto" })
+        );
+    }
+
+    #[test]
+    fn serializes_per_run_overrides() {
+        let req = CreateRunRequest::new("asst_abc123".to_owned());
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({ "assistant_id": "asst_abc123" })
+        );
+
+        let req = req
+            .additional_instructions("Address the user as Jane.".to_owned())
+            .additional_messages(vec![Message {
+                role: crate::common::MessageRole::User,
+                content: "And tomorrow?".to_owned(),
+                file_ids: None,
+                metadata: None,
+            }])
+            .temperature(0.5)
+            .top_p(0.9)
+            .response_format(serde_json::json!({ "type": "json_object" }));
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "assistant_id": "asst_abc123",
+                "additional_instructions": "Address the user as Jane.",
+                "additional_messages": [{ "role": "user", "content": "And tomorrow?" }],
+                "temperature": 0.5,
+                "top_p": 0.9,
+                "response_format": { "type": "json_object" }
+            })
+        );
+    }
+}
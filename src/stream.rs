@@ -0,0 +1,249 @@
+//! This module parses server-sent event (SSE) streams returned by endpoints called with `stream: true`.
+//! Each `data:` event is deserialized into the expected chunk type until the `[DONE]` sentinel is received.
+//! Events may arrive split at any byte, use `\n` or `\r\n` line endings, and spread their payload
+//! over several `data:` lines, which are joined with newlines.
+
+use crate::error::APIError;
+use futures_util::{stream, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::{pin::Pin, time::Duration};
+
+/// Sentinel sent by the API once a stream has no more events.
+const DONE: &str = "[DONE]";
+
+/// State carried between polls of an SSE stream.
+struct SseState<S> {
+    /// Underlying stream of raw response bytes.
+    bytes: Pin<Box<S>>,
+    /// Bytes received but not yet parsed into a complete event.
+    buffer: Vec<u8>,
+    /// Start of the first line of `buffer` not known to be complete, where the search for the
+    /// end of the current event resumes once more bytes arrive.
+    scanned: usize,
+    /// Whether the stream has finished.
+    done: bool,
+}
+
+/// Represents a single server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    /// Optional event name from the `event:` field.
+    pub event: Option<String>,
+    /// Payload joined from the `data:` lines.
+    pub data: String,
+}
+
+/// Parses the `event:` and `data:` fields of a single raw event, if it carries any data.
+fn parse_event(raw: &[u8]) -> Option<SseEvent> {
+    let raw = String::from_utf8_lossy(raw);
+    let mut event = None;
+    let mut data = Vec::new();
+    for line in raw.lines() {
+        if let Some(name) = line.strip_prefix("event:") {
+            event = Some(name.trim().to_owned());
+        } else if let Some(payload) = line.strip_prefix("data:") {
+            data.push(payload.strip_prefix(' ').unwrap_or(payload));
+        }
+    }
+    if data.is_empty() {
+        None
+    } else {
+        Some(SseEvent {
+            event,
+            data: data.join("\n"),
+        })
+    }
+}
+
+/// Removes the first complete event from the buffer, if one has been fully received.
+///
+/// Events end with a blank line, terminated by either `\n` or `\r\n`, so a separator split
+/// across two network chunks is only recognized once both halves have arrived. `scanned` keeps
+/// track of the lines already searched, so an event received over many chunks is scanned once.
+fn next_event(buffer: &mut Vec<u8>, scanned: &mut usize) -> Option<Vec<u8>> {
+    let mut line_start = *scanned;
+    for index in *scanned..buffer.len() {
+        if buffer[index] != b'\n' {
+            continue;
+        }
+        let line = &buffer[line_start..index];
+        if line.is_empty() || line == b"\r" {
+            let event = buffer[..line_start].to_vec();
+            buffer.drain(..=index);
+            *scanned = 0;
+            return Some(event);
+        }
+        line_start = index + 1;
+    }
+    *scanned = line_start;
+    None
+}
+
+/// Fails a stream of raw response bytes with `APIError::Timeout` if no chunk arrives within
+/// `read_timeout` of the previous one.
+pub(crate) fn with_read_timeout<S, B>(
+    bytes: S,
+    read_timeout: Option<Duration>,
+) -> impl Stream<Item = Result<B, APIError>>
+where
+    S: Stream<Item = reqwest::Result<B>>,
+{
+    stream::unfold(
+        (Box::pin(bytes), false),
+        move |(mut bytes, done)| async move {
+            if done {
+                return None;
+            }
+            let next = match read_timeout {
+                Some(read_timeout) => {
+                    match tokio::time::timeout(read_timeout, bytes.next()).await {
+                        Ok(next) => next,
+                        Err(_) => {
+                            return Some((
+                                Err(APIError::Timeout(read_timeout)),
+                                (bytes, true),
+                            ))
+                        }
+                    }
+                }
+                None => bytes.next().await,
+            };
+            next.map(|chunk| (chunk.map_err(APIError::ReqwestError), (bytes, false)))
+        },
+    )
+}
+
+/// Turns a stream of raw response bytes into a stream of server-sent events.
+pub(crate) fn sse_events<S, B>(bytes: S) -> impl Stream<Item = Result<SseEvent, APIError>>
+where
+    S: Stream<Item = Result<B, APIError>>,
+    B: AsRef<[u8]>,
+{
+    let state = SseState {
+        bytes: Box::pin(bytes),
+        buffer: Vec::new(),
+        scanned: 0,
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if state.done {
+                return None;
+            }
+            let raw = match next_event(&mut state.buffer, &mut state.scanned) {
+                Some(raw) => raw,
+                None => match state.bytes.next().await {
+                    Some(Ok(chunk)) => {
+                        state.buffer.extend_from_slice(chunk.as_ref());
+                        continue;
+                    }
+                    Some(Err(err)) => {
+                        state.done = true;
+                        return Some((Err(err), state));
+                    }
+                    None => {
+                        state.done = true;
+                        std::mem::take(&mut state.buffer)
+                    }
+                },
+            };
+            match parse_event(&raw) {
+                Some(event) if event.data == DONE => return None,
+                Some(event) => return Some((Ok(event), state)),
+                None => continue,
+            }
+        }
+    })
+}
+
+/// Turns a stream of raw response bytes into a stream of deserialized SSE payloads.
+pub(crate) fn sse_stream<T, S, B>(bytes: S) -> impl Stream<Item = Result<T, APIError>>
+where
+    T: DeserializeOwned,
+    S: Stream<Item = Result<B, APIError>>,
+    B: AsRef<[u8]>,
+{
+    sse_events(bytes).map(|event| {
+        event.and_then(|event| {
+            serde_json::from_str::<T>(&event.data).map_err(APIError::SerdeError)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects the events parsed from `chunks`, fed to `sse_events` one chunk at a time.
+    async fn collect(chunks: Vec<Vec<u8>>) -> Vec<SseEvent> {
+        let bytes = stream::iter(chunks.into_iter().map(Ok::<_, APIError>));
+        sse_events(bytes)
+            .map(|event| event.expect("event parses"))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn parses_events_split_at_every_byte_offset() {
+        let body: &[u8] =
+            b"event: message\ndata: {\"a\":1}\r\n\r\ndata: first\ndata: second\n\ndata: [DONE]\n\n";
+        let expected = collect(vec![body.to_vec()]).await;
+        assert_eq!(
+            expected,
+            vec![
+                SseEvent {
+                    event: Some("message".to_owned()),
+                    data: "{\"a\":1}".to_owned(),
+                },
+                SseEvent {
+                    event: None,
+                    data: "first\nsecond".to_owned(),
+                },
+            ]
+        );
+        for split in 1..body.len() {
+            let chunks = vec![body[..split].to_vec(), body[split..].to_vec()];
+            assert_eq!(collect(chunks).await, expected, "split at byte {}", split);
+        }
+        let bytewise = body.iter().map(|byte| vec![*byte]).collect();
+        assert_eq!(collect(bytewise).await, expected);
+    }
+
+    #[test]
+    fn parses_crlf_line_endings() {
+        let mut buffer = b"event: delta\r\ndata: hi\r\n\r\nrest".to_vec();
+        let mut scanned = 0;
+        let raw = next_event(&mut buffer, &mut scanned).expect("event is complete");
+        assert_eq!(buffer, b"rest");
+        assert_eq!(
+            parse_event(&raw),
+            Some(SseEvent {
+                event: Some("delta".to_owned()),
+                data: "hi".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn joins_multi_line_data() {
+        let event =
+            parse_event(b"data: {\"a\":\ndata:1}\n: comment\n").expect("has data");
+        assert_eq!(event.event, None);
+        assert_eq!(event.data, "{\"a\":\n1}");
+    }
+
+    #[test]
+    fn waits_for_crlf_separator_split_across_chunks() {
+        let mut buffer = b"data: hi\r\n\r".to_vec();
+        let mut scanned = 0;
+        assert_eq!(next_event(&mut buffer, &mut scanned), None);
+        buffer.extend_from_slice(b"\ndata: next");
+        let raw = next_event(&mut buffer, &mut scanned).expect("event is complete");
+        assert_eq!(
+            parse_event(&raw).map(|event| event.data),
+            Some("hi".to_owned())
+        );
+        assert_eq!(buffer, b"data: next");
+        assert_eq!(next_event(&mut buffer, &mut scanned), None);
+    }
+}
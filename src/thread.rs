@@ -1,12 +1,13 @@
 //! This module defines the structures and methods for creating and modifying threads, as well as the message format used within threads.
 //! It includes:
 //! - `CreateThreadRequest`: Struct for creating a new thread with optional messages and metadata.
+//! - `CreateThreadRequest::with_user_message` / `add_message`: Helpers building the initial messages.
 //! - `ThreadObject`: Struct representing a thread object with various attributes.
 //! - `Message`: Struct for messages within a thread, including role, content, and optional metadata.
 //! - `ModifyThreadRequest`: Struct for modifying an existing thread's metadata.
 //! - `impl_builder_methods!`: Macro for generating builder methods for structs.
 
-use crate::common::MessageRole;
+use crate::common::{MessageRole, ObjectType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -31,6 +32,22 @@ impl CreateThreadRequest {
             metadata: None,
         }
     }
+
+    /// Creates a new `CreateThreadRequest` starting with a single user message.
+    pub fn with_user_message(text: impl Into<String>) -> Self {
+        Self::new().add_message(MessageRole::User, text)
+    }
+
+    /// Appends a message with the given role and content to the thread's initial messages.
+    pub fn add_message(mut self, role: MessageRole, content: impl Into<String>) -> Self {
+        self.messages.get_or_insert_with(Vec::new).push(Message {
+            role,
+            content: content.into(),
+            file_ids: None,
+            metadata: None,
+        });
+        self
+    }
 }
 
 impl Default for CreateThreadRequest {
@@ -51,8 +68,8 @@ impl_builder_methods!(
 pub struct ThreadObject {
     /// Unique identifier for the thread.
     pub id: String,
-    /// Object type, typically "thread".
-    pub object: String,
+    /// Object type, typically `ObjectType::Thread`.
+    pub object: ObjectType,
     /// Timestamp of when the thread was created.
     pub created_at: i64,
     /// Metadata associated with the thread.
@@ -95,3 +112,31 @@ impl_builder_methods!(
     ModifyThreadRequest,
     metadata: HashMap<String, String>
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_messages_in_order() {
+        assert_eq!(
+            serde_json::to_value(CreateThreadRequest::new()).unwrap(),
+            json!({})
+        );
+
+        let req = CreateThreadRequest::with_user_message("Hello")
+            .add_message(MessageRole::Assistant, "Hi! How can I help?")
+            .add_message(MessageRole::User, "What's the weather?");
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({
+                "messages": [
+                    { "role": "user", "content": "Hello" },
+                    { "role": "assistant", "content": "Hi! How can I help?" },
+                    { "role": "user", "content": "What's the weather?" }
+                ]
+            })
+        );
+    }
+}
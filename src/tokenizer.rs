@@ -0,0 +1,148 @@
+//! This module provides local token counting for chat messages, so prompts can be sized before
+//! they are sent to the API.
+//! It includes:
+//! - `count_tokens`: Function estimating the prompt tokens consumed by a list of chat messages.
+//! - `count_text_tokens`: Function counting the tokens of a single piece of text for a model.
+
+use crate::{
+    chat_completion::{ChatCompletionMessage, Content},
+    models::Model,
+};
+use tiktoken_rs::{
+    cl100k_base_singleton, o200k_base_singleton, p50k_base_singleton,
+    p50k_edit_singleton, r50k_base_singleton, tokenizer::Tokenizer, CoreBPE,
+};
+
+/// Number of tokens every message is wrapped in, regardless of its content.
+const TOKENS_PER_MESSAGE: usize = 3;
+
+/// Number of extra tokens consumed when a message carries a `name`.
+const TOKENS_PER_NAME: usize = 1;
+
+/// Number of tokens used to prime the assistant's reply.
+const TOKENS_PER_REPLY: usize = 3;
+
+/// Runs `f` with the shared BPE encoder for the given model name, defaulting to `cl100k_base`.
+fn with_encoder<R>(model: &str, f: impl FnOnce(&CoreBPE) -> R) -> R {
+    match tiktoken_rs::tokenizer::get_tokenizer(model) {
+        Some(Tokenizer::O200kBase) => f(&o200k_base_singleton().lock()),
+        Some(Tokenizer::P50kBase) => f(&p50k_base_singleton().lock()),
+        Some(Tokenizer::P50kEdit) => f(&p50k_edit_singleton().lock()),
+        Some(Tokenizer::R50kBase) | Some(Tokenizer::Gpt2) => {
+            f(&r50k_base_singleton().lock())
+        }
+        Some(Tokenizer::Cl100kBase) | None => f(&cl100k_base_singleton().lock()),
+    }
+}
+
+/// Counts the tokens of a single piece of text for the given model.
+pub fn count_text_tokens(model: &Model, text: &str) -> usize {
+    with_encoder(&model.to_string(), |bpe| {
+        bpe.encode_with_special_tokens(text).len()
+    })
+}
+
+/// Estimates the prompt tokens consumed by the given messages, including per-message overhead.
+///
+/// Image parts are not counted, only the text they carry.
+pub fn count_tokens(model: &Model, messages: &[ChatCompletionMessage]) -> usize {
+    count_tokens_for(&model.to_string(), messages)
+}
+
+/// Estimates the prompt tokens consumed by the given messages for a raw model name.
+pub(crate) fn count_tokens_for(model: &str, messages: &[ChatCompletionMessage]) -> usize {
+    with_encoder(model, |bpe| {
+        let count = |text: &str| bpe.encode_with_special_tokens(text).len();
+
+        let mut total = TOKENS_PER_REPLY;
+        for message in messages {
+            total += TOKENS_PER_MESSAGE + count(message.role.as_ref());
+            match &message.content {
+                Content::Text(text) => total += count(text),
+                Content::ImageUrl(parts) => {
+                    for text in parts.iter().filter_map(|part| part.text.as_deref()) {
+                        total += count(text);
+                    }
+                }
+            }
+            if let Some(name) = &message.name {
+                total += TOKENS_PER_NAME + count(name);
+            }
+        }
+        total
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::MessageRole;
+
+    fn message(
+        role: MessageRole,
+        name: Option<&str>,
+        text: &str,
+    ) -> ChatCompletionMessage {
+        ChatCompletionMessage {
+            role,
+            content: Content::Text(text.to_owned()),
+            name: name.map(str::to_owned),
+            tool_call_id: None,
+            tool_calls: None,
+        }
+    }
+
+    #[test]
+    fn counts_text_tokens() {
+        let model = Model::custom("gpt-4");
+        assert_eq!(count_text_tokens(&model, "hello world"), 2);
+        assert_eq!(count_text_tokens(&model, ""), 0);
+    }
+
+    #[test]
+    fn counts_message_overhead() {
+        let messages = [message(MessageRole::User, None, "hello world")];
+        // 3 per message + 1 for the role + 2 for the content + 3 to prime the reply.
+        assert_eq!(count_tokens(&Model::custom("gpt-4"), &messages), 9);
+    }
+
+    #[test]
+    fn matches_published_chat_fixture() {
+        let messages = [
+            message(
+                MessageRole::System,
+                None,
+                "You are a helpful, pattern-following assistant that translates corporate \
+                 jargon into plain English.",
+            ),
+            message(
+                MessageRole::System,
+                Some("example_user"),
+                "New synergies will help drive top-line growth.",
+            ),
+            message(
+                MessageRole::System,
+                Some("example_assistant"),
+                "Things working well together will increase revenue.",
+            ),
+            message(
+                MessageRole::System,
+                Some("example_user"),
+                "Let's circle back when we have more bandwidth to touch base on \
+                 opportunities for increased leverage.",
+            ),
+            message(
+                MessageRole::System,
+                Some("example_assistant"),
+                "Let's talk later when we're less busy about how to do better.",
+            ),
+            message(
+                MessageRole::User,
+                None,
+                "This late pivot means we don't have time to boil the ocean for the client \
+                 deliverable.",
+            ),
+        ];
+        assert_eq!(count_tokens(&Model::custom("gpt-4-0613"), &messages), 129);
+    }
+}
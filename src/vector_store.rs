@@ -0,0 +1,202 @@
+//! This module defines the structures for managing vector stores, which back the file search
+//! tool of assistants.
+//! It includes:
+//! - `CreateVectorStoreRequest`: Struct for creating a new vector store.
+//! - `VectorStoreExpiration`: Struct describing when an idle vector store expires.
+//! - `VectorStoreObject`: Struct representing a vector store and its indexing progress.
+//! - `VectorStoreFileCounts`: Struct tracking the files of a vector store by status.
+//! - `ListVectorStore`: Struct for a page of vector stores.
+//! - `CreateVectorStoreFileRequest`: Struct for adding an uploaded file to a vector store.
+//! - `VectorStoreFileObject`: Struct representing a file attached to a vector store.
+//! - `VectorStoreFileError`: Struct describing why a file could not be indexed.
+//! - `impl_builder_methods!`: Macro for generating builder methods for structs.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::impl_builder_methods;
+
+/// Represents a request to create a new vector store.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CreateVectorStoreRequest {
+    /// Optional name of the vector store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Optional IDs of uploaded files to index into the vector store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_ids: Option<Vec<String>>,
+    /// Optional expiration policy of the vector store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<VectorStoreExpiration>,
+    /// Optional key-value pairs attached to the vector store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl CreateVectorStoreRequest {
+    /// Creates a new, empty `CreateVectorStoreRequest`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl_builder_methods!(
+    CreateVectorStoreRequest,
+    name: String,
+    file_ids: Vec<String>,
+    expires_after: VectorStoreExpiration,
+    metadata: HashMap<String, String>
+);
+
+/// Represents when an idle vector store expires.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct VectorStoreExpiration {
+    /// Timestamp the expiration is counted from, currently only `last_active_at`.
+    pub anchor: String,
+    /// Number of days after the anchor when the vector store expires.
+    pub days: i64,
+}
+
+impl VectorStoreExpiration {
+    /// Creates a `VectorStoreExpiration` expiring the given number of days after last use.
+    pub fn after_last_active(days: i64) -> Self {
+        Self {
+            anchor: "last_active_at".to_owned(),
+            days,
+        }
+    }
+}
+
+/// Represents a vector store and its indexing progress.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VectorStoreObject {
+    /// Unique identifier for the vector store.
+    pub id: String,
+    /// Object type, typically "vector_store".
+    pub object: String,
+    /// Timestamp of when the vector store was created.
+    pub created_at: i64,
+    /// Optional name of the vector store.
+    pub name: Option<String>,
+    /// Total number of bytes used by the files in the vector store.
+    pub usage_bytes: i64,
+    /// Counts of the files in the vector store by status.
+    pub file_counts: VectorStoreFileCounts,
+    /// Status of the vector store, such as `in_progress` or `completed`.
+    pub status: String,
+    /// Optional expiration policy of the vector store.
+    pub expires_after: Option<VectorStoreExpiration>,
+    /// Optional timestamp of when the vector store expires.
+    pub expires_at: Option<i64>,
+    /// Optional timestamp of when the vector store was last used.
+    pub last_active_at: Option<i64>,
+    /// Optional key-value pairs attached to the vector store.
+    pub metadata: Option<HashMap<String, String>>,
+    /// Optional headers from the response.
+    pub headers: Option<HashMap<String, String>>,
+}
+
+/// Represents the files of a vector store by status.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VectorStoreFileCounts {
+    /// Number of files still being indexed.
+    pub in_progress: i64,
+    /// Number of files indexed successfully.
+    pub completed: i64,
+    /// Number of files that failed to index.
+    pub failed: i64,
+    /// Number of files whose indexing was cancelled.
+    pub cancelled: i64,
+    /// Total number of files.
+    pub total: i64,
+}
+
+/// Represents a page of vector stores.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListVectorStore {
+    /// Object type, typically "list".
+    pub object: String,
+    /// List of vector store objects.
+    pub data: Vec<VectorStoreObject>,
+    /// Optional ID of the first vector store in the page.
+    pub first_id: Option<String>,
+    /// Optional ID of the last vector store in the page.
+    pub last_id: Option<String>,
+    /// Indicates if there are more vector stores available.
+    pub has_more: bool,
+    /// Optional headers from the response.
+    pub headers: Option<HashMap<String, String>>,
+}
+
+/// Represents a request to add an uploaded file to a vector store.
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateVectorStoreFileRequest {
+    /// ID of the uploaded file to index.
+    pub file_id: String,
+}
+
+impl CreateVectorStoreFileRequest {
+    /// Creates a new `CreateVectorStoreFileRequest` for the given file ID.
+    pub fn new(file_id: String) -> Self {
+        Self { file_id }
+    }
+}
+
+/// Represents a file attached to a vector store.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VectorStoreFileObject {
+    /// Unique identifier for the file.
+    pub id: String,
+    /// Object type, typically "vector_store.file".
+    pub object: String,
+    /// Number of bytes the file uses in the vector store.
+    pub usage_bytes: i64,
+    /// Timestamp of when the file was added.
+    pub created_at: i64,
+    /// ID of the vector store the file belongs to.
+    pub vector_store_id: String,
+    /// Status of the file, such as `in_progress` or `completed`.
+    pub status: String,
+    /// Optional error explaining why the file could not be indexed.
+    pub last_error: Option<VectorStoreFileError>,
+    /// Optional headers from the response.
+    pub headers: Option<HashMap<String, String>>,
+}
+
+/// Represents why a file could not be indexed into a vector store.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VectorStoreFileError {
+    /// Machine-readable error code.
+    pub code: String,
+    /// Human-readable description of the error.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_create_request() {
+        assert_eq!(
+            serde_json::to_value(CreateVectorStoreRequest::new()).unwrap(),
+            json!({})
+        );
+
+        let req = CreateVectorStoreRequest::new()
+            .name("Support FAQ".to_owned())
+            .file_ids(vec!["file-abc123".to_owned()])
+            .expires_after(VectorStoreExpiration::after_last_active(7))
+            .metadata(HashMap::from([("team".to_owned(), "support".to_owned())]));
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            json!({
+                "name": "Support FAQ",
+                "file_ids": ["file-abc123"],
+                "expires_after": { "anchor": "last_active_at", "days": 7 },
+                "metadata": { "team": "support" }
+            })
+        );
+    }
+}
@@ -0,0 +1,830 @@
+#![cfg(feature = "mock")]
+
+use futures_util::StreamExt;
+use openai_rst::{
+    assistant::AssistantRequest,
+    audio::{AudioTranscriptionRequest, AudioTranslationRequest},
+    chat_completion::ChatCompletionRequest,
+    client::{Client, RequestOptions},
+    completion::CompletionRequest,
+    edit::EditRequest,
+    error::APIError,
+    file::{FileDeleteRequest, FilePurpose},
+    mock::MockServer,
+    models::{Model, GPT4},
+    moderation::{CreateModerationRequest, ModerationInputItem},
+    run::{CreateRunRequest, CreateThreadAndRunRequest},
+    thread::CreateThreadRequest,
+};
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+const ASSISTANT: &str = r#"{
+    "id": "asst_abc123",
+    "object": "assistant",
+    "created_at": 1698984975,
+    "name": null,
+    "description": null,
+    "model": "gpt-4o",
+    "instructions": null,
+    "tools": [],
+    "tool_resources": {},
+    "metadata": {}
+}"#;
+
+#[tokio::test]
+async fn create_assistant_sends_assistants_beta_header() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(Method::POST, "/assistants", 200, ASSISTANT);
+    let client = server.client().unwrap();
+
+    let assistant = client
+        .create_assistant(AssistantRequest::new(Model::custom("gpt-4o")))
+        .await
+        .unwrap();
+
+    assert_eq!(assistant.id, "asst_abc123");
+    let requests = server.received_requests();
+    assert_eq!(requests[0].headers["openai-beta"], "assistants=v2");
+}
+
+fn run(status: &str) -> String {
+    format!(
+        r#"{{
+            "id": "run_abc123",
+            "object": "thread.run",
+            "created_at": 1699063290,
+            "assistant_id": "asst_abc123",
+            "thread_id": "thread_abc123",
+            "status": "{}",
+            "model": "gpt-4o",
+            "instructions": null,
+            "tools": [],
+            "metadata": {{}},
+            "required_action": {{
+                "type": "submit_tool_outputs",
+                "submit_tool_outputs": {{"tool_calls": []}}
+            }}
+        }}"#,
+        status
+    )
+}
+
+#[tokio::test]
+async fn wait_for_run_returns_on_requires_action() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::GET,
+        "/threads/thread_abc123/runs/run_abc123",
+        200,
+        run("requires_action"),
+    );
+    let client = server.client().unwrap();
+
+    let run = client
+        .wait_for_run(
+            "thread_abc123".to_owned(),
+            "run_abc123".to_owned(),
+            Duration::from_millis(1),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(run.status, "requires_action");
+    assert!(run.required_action.is_some());
+}
+
+#[tokio::test]
+async fn create_moderation_posts_to_moderations() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::POST,
+        "/moderations",
+        200,
+        r#"{
+            "id": "modr-123",
+            "model": "omni-moderation-latest",
+            "results": [{
+                "flagged": false,
+                "categories": {
+                    "hate": false, "hate/threatening": false, "self-harm": false,
+                    "sexual": false, "sexual/minors": false, "violence": false,
+                    "violence/graphic": false
+                },
+                "category_scores": {
+                    "hate": 0.0, "hate/threatening": 0.0, "self-harm": 0.0,
+                    "sexual": 0.0, "sexual/minors": 0.0, "violence": 0.0,
+                    "violence/graphic": 0.0
+                }
+            }]
+        }"#,
+    );
+    let client = server.client().unwrap();
+
+    let response = client
+        .create_moderation(CreateModerationRequest::new(vec![
+            ModerationInputItem::image_url("https://example.com/image.png"),
+        ]))
+        .await
+        .unwrap();
+
+    assert!(!response.results[0].flagged);
+    let requests = server.received_requests();
+    assert_eq!(requests[0].path, "/moderations");
+    assert_eq!(requests[0].json().unwrap()["input"][0]["type"], "image_url");
+}
+
+#[tokio::test]
+async fn audio_translation_uploads_multipart_form() {
+    let path = std::env::temp_dir().join("openai-rst-translation.mp3");
+    std::fs::write(&path, b"fake audio").unwrap();
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::POST,
+        "/audio/translations",
+        200,
+        r#"{"text": "Hello"}"#,
+    );
+    let client = server.client().unwrap();
+
+    let response = client
+        .audio_translation(AudioTranslationRequest::new(
+            path.to_string_lossy().into_owned(),
+            "whisper-1".to_owned(),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.text, "Hello");
+    let request = &server.received_requests()[0];
+    assert!(request.headers["content-type"].starts_with("multipart/form-data; boundary="));
+    let body = String::from_utf8_lossy(&request.body);
+    assert!(body.contains("filename=\"openai-rst-translation.mp3\""));
+    assert!(body.contains("fake audio"));
+    assert!(body.contains("whisper-1"));
+    std::fs::remove_file(path).unwrap();
+}
+
+fn chat_request() -> ChatCompletionRequest {
+    ChatCompletionRequest::new(Model::GPT4(GPT4::GPT4o), "Hello".into())
+}
+
+#[tokio::test]
+async fn chat_completion_sends_messages_and_reads_reply() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion("Hello from the mock server");
+    let client = server.client().unwrap();
+
+    let result = client.chat_completion(chat_request()).await.unwrap();
+
+    assert_eq!(
+        result.choices[0].message.content.as_deref(),
+        Some("Hello from the mock server")
+    );
+    let requests = server.received_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/chat/completions");
+    assert_eq!(
+        requests[0].json().unwrap()["messages"][0]["content"],
+        "Hello"
+    );
+    assert_eq!(requests[0].headers["authorization"], "Bearer test-key");
+}
+
+#[tokio::test]
+async fn chat_completion_stream_yields_deltas() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion_stream(&["Hello", " again"]);
+    let client = server.client().unwrap();
+
+    let mut stream = client.chat_completion_stream(chat_request()).await.unwrap();
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        for choice in chunk.unwrap().choices {
+            text.push_str(choice.delta.content.as_deref().unwrap_or_default());
+        }
+    }
+
+    assert_eq!(text, "Hello again");
+}
+
+#[tokio::test]
+async fn chat_completion_surfaces_api_errors() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_error(Method::POST, "/chat/completions", 429, "Rate limit reached");
+    let client = server.client().unwrap();
+
+    match client.chat_completion(chat_request()).await {
+        Err(APIError::ApiError { status, .. }) => assert_eq!(status, 429),
+        other => panic!("expected a 429 error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn retried_create_reuses_idempotency_key() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_error(
+        Method::POST,
+        "/threads/thread_abc123/runs",
+        500,
+        "Server error",
+    );
+    let client = server.client().unwrap();
+    let opts = RequestOptions::new().with_generated_idempotency_key();
+    let req = CreateRunRequest::new("asst_abc123".to_owned());
+
+    let first = client
+        .create_run_with_opts("thread_abc123".to_owned(), req.clone(), &opts)
+        .await;
+    assert!(first.is_err());
+    server.mock(
+        Method::POST,
+        "/threads/thread_abc123/runs",
+        200,
+        run("queued"),
+    );
+    client
+        .create_run_with_opts("thread_abc123".to_owned(), req, &opts)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests();
+    assert_eq!(requests.len(), 2);
+    let key = &requests[0].headers["idempotency-key"];
+    assert_eq!(Some(key), opts.idempotency_key.as_ref());
+    assert_eq!(&requests[1].headers["idempotency-key"], key);
+}
+
+#[tokio::test]
+async fn file_upload_bytes_sends_multipart_form() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::POST,
+        "/files",
+        200,
+        r#"{
+            "id": "file-abc123",
+            "object": "file",
+            "bytes": 5,
+            "created_at": 1677610602,
+            "filename": "data.jsonl",
+            "purpose": "batch"
+        }"#,
+    );
+    let client = server.client().unwrap();
+
+    let file = client
+        .file_upload_bytes(
+            b"{}\n".to_vec(),
+            "data.jsonl".to_owned(),
+            FilePurpose::Batch,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(file.id, "file-abc123");
+    let requests = server.received_requests();
+    let content_type = &requests[0].headers["content-type"];
+    assert!(content_type.starts_with("multipart/form-data; boundary="));
+    assert_ne!(content_type, "application/json");
+}
+
+#[tokio::test]
+async fn verify_key_accepts_valid_key() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::GET,
+        "/models",
+        200,
+        r#"{"object": "list", "data": []}"#,
+    );
+    let client = server.client().unwrap();
+
+    assert!(client.verify_key().await.unwrap());
+    assert_eq!(server.received_requests()[0].path, "/models");
+}
+
+#[tokio::test]
+async fn verify_key_rejects_invalid_key() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_error(Method::GET, "/models", 401, "Incorrect API key provided");
+    let client = server.client().unwrap();
+
+    match client.verify_key().await {
+        Err(APIError::ApiError { status, .. }) => assert_eq!(status, 401),
+        other => panic!("expected a 401 error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn audio_transcription_repeats_timestamp_granularities() {
+    let path = std::env::temp_dir().join("openai-rst-transcription.mp3");
+    std::fs::write(&path, b"fake audio").unwrap();
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::POST,
+        "/audio/transcriptions",
+        200,
+        r#"{"text": "Hello"}"#,
+    );
+    let client = server.client().unwrap();
+
+    let req = AudioTranscriptionRequest::new(
+        path.to_string_lossy().into_owned(),
+        "whisper-1".to_owned(),
+    )
+    .response_format("verbose_json".to_owned())
+    .timestamp_granularities(vec!["word".to_owned(), "segment".to_owned()]);
+    client.audio_transcription(req).await.unwrap();
+
+    let request = &server.received_requests()[0];
+    let body = String::from_utf8_lossy(&request.body);
+    let field = "name=\"timestamp_granularities[]\"\r\n\r\n";
+    assert_eq!(body.matches(field).count(), 2);
+    assert!(body.contains(&format!("{}word\r\n", field)));
+    assert!(body.contains(&format!("{}segment\r\n", field)));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn delete_treats_empty_204_as_deleted() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(Method::DELETE, "/assistants/asst_abc123", 204, "");
+    server.mock(Method::DELETE, "/threads/thread_abc123", 200, "");
+    let client = server.client().unwrap();
+
+    let assistant = client
+        .delete_assistant("asst_abc123".to_owned())
+        .await
+        .unwrap();
+    assert!(assistant.deleted);
+    assert_eq!(assistant.id, "asst_abc123");
+    assert_eq!(assistant.object, "assistant.deleted");
+
+    let thread = client
+        .delete_thread("thread_abc123".to_owned())
+        .await
+        .unwrap();
+    assert!(thread.deleted);
+    assert_eq!(thread.object, "thread.deleted");
+}
+
+#[tokio::test]
+async fn file_delete_reads_json_and_empty_responses() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::DELETE,
+        "/files/file-abc123",
+        200,
+        r#"{"id": "file-abc123", "object": "file", "deleted": true}"#,
+    );
+    server.mock(Method::DELETE, "/files/file-xyz789", 204, "");
+    let client = server.client().unwrap();
+
+    let json = client
+        .file_delete(FileDeleteRequest::new("file-abc123".to_owned()))
+        .await
+        .unwrap();
+    assert!(json.deleted);
+    assert_eq!(json.id, "file-abc123");
+
+    let empty = client
+        .file_delete(FileDeleteRequest::new("file-xyz789".to_owned()))
+        .await
+        .unwrap();
+    assert!(empty.deleted);
+    assert_eq!(empty.id, "file-xyz789");
+}
+
+#[tokio::test]
+async fn file_list_filtered_sends_purpose_query() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::GET,
+        "/files",
+        200,
+        r#"{"object": "list", "data": []}"#,
+    );
+    let client = server.client().unwrap();
+
+    client.file_list().await.unwrap();
+    client
+        .file_list_filtered(Some(FilePurpose::FineTune))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests();
+    assert_eq!(requests[0].path, "/files");
+    assert_eq!(requests[1].path, "/files?purpose=fine-tune");
+}
+
+#[tokio::test]
+async fn completion_stream_yields_text_chunks() {
+    let server = MockServer::start().await.unwrap();
+    let chunk = |text: &str, finish_reason: Value| {
+        let chunk = json!({
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 0,
+            "model": "gpt-3.5-turbo-instruct",
+            "choices": [{
+                "text": text,
+                "index": 0,
+                "logprobs": null,
+                "finish_reason": finish_reason
+            }]
+        });
+        format!("data: {}\n\n", chunk)
+    };
+    let body = format!(
+        "{}{}data: [DONE]\n\n",
+        chunk("Hello", Value::Null),
+        chunk(" there", json!("stop"))
+    );
+    server.mock_with_headers(
+        Method::POST,
+        "/completions",
+        200,
+        vec![("content-type".to_owned(), "text/event-stream".to_owned())],
+        body,
+    );
+    let client = server.client().unwrap();
+
+    let req = CompletionRequest::new(Model::custom("gpt-3.5-turbo-instruct"), "Say hi");
+    let chunks: Vec<_> = client
+        .completion_stream(req)
+        .await
+        .unwrap()
+        .map(|chunk| chunk.unwrap())
+        .collect()
+        .await;
+
+    let text: Vec<_> = chunks
+        .iter()
+        .map(|chunk| chunk.choices[0].text.as_str())
+        .collect();
+    assert_eq!(text, ["Hello", " there"]);
+    assert!(chunks[0].choices[0].finish_reason.is_none());
+    assert_eq!(
+        server.received_requests()[0].json().unwrap()["stream"],
+        true
+    );
+}
+
+#[tokio::test]
+async fn delete_model_sends_delete_to_model_url() {
+    let server = MockServer::start().await.unwrap();
+    let model = "ft:gpt-4o-mini-2024-07-18:acme::abc123";
+    server.mock(
+        Method::DELETE,
+        &format!("/models/{}", model),
+        200,
+        json!({ "id": model, "object": "model", "deleted": true }).to_string(),
+    );
+    let client = server.client().unwrap();
+
+    let status = client.delete_model(model.to_owned()).await.unwrap();
+
+    assert!(status.deleted);
+    assert_eq!(status.id, model);
+    let request = &server.received_requests()[0];
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, format!("/models/{}", model));
+}
+
+#[tokio::test]
+async fn builder_applies_timeouts() {
+    let server = MockServer::start().await.unwrap();
+    let client = server
+        .client_builder()
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(2))
+        .read_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.timeout, Some(Duration::from_secs(30)));
+    assert_eq!(client.connect_timeout, Some(Duration::from_secs(2)));
+    assert_eq!(client.read_timeout, Some(Duration::from_secs(5)));
+}
+
+#[tokio::test]
+async fn read_timeout_fails_delayed_response() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion("Too late");
+    server.set_response_delay(Duration::from_millis(500));
+    let client = server
+        .client_builder()
+        .read_timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    match client.chat_completion(chat_request()).await {
+        Err(APIError::Timeout(timeout)) => assert_eq!(timeout, Duration::from_millis(50)),
+        other => panic!("expected a read timeout, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn streaming_skips_overall_timeout() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion_stream(&["Slow", " but fine"]);
+    server.set_response_delay(Duration::from_millis(300));
+    let client = server
+        .client_builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let mut stream = client.chat_completion_stream(chat_request()).await.unwrap();
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        for choice in chunk.unwrap().choices {
+            text.push_str(choice.delta.content.as_deref().unwrap_or_default());
+        }
+    }
+    assert_eq!(text, "Slow but fine");
+
+    server.mock_chat_completion("Too late");
+    match client.chat_completion(chat_request()).await {
+        Err(APIError::ReqwestError(err)) => assert!(err.is_timeout()),
+        other => panic!("expected the overall timeout, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn chat_completion_with_cancel_stops_before_completion() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion("Too late");
+    server.set_response_delay(Duration::from_secs(5));
+    let client = server.client().unwrap();
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let result = client
+        .chat_completion_with_cancel(chat_request(), cancel)
+        .await;
+    assert!(matches!(result, Err(APIError::Cancelled)), "{:?}", result);
+
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        trigger.cancel();
+    });
+    let result = client
+        .chat_completion_with_cancel(chat_request(), cancel)
+        .await;
+    assert!(matches!(result, Err(APIError::Cancelled)), "{:?}", result);
+}
+
+#[tokio::test]
+async fn builder_routes_requests_through_proxy() {
+    let proxy = MockServer::start().await.unwrap();
+    proxy.mock(
+        Method::GET,
+        "http://api.openai.invalid/v1/models",
+        200,
+        "{}",
+    );
+    let client = Client::builder("test-key".to_owned())
+        .endpoint("http://api.openai.invalid/v1".to_owned())
+        .proxy(reqwest::Proxy::http(proxy.uri()).unwrap())
+        .build()
+        .unwrap();
+
+    assert!(client.verify_key().await.unwrap());
+
+    let request = &proxy.received_requests()[0];
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, "http://api.openai.invalid/v1/models");
+}
+
+#[tokio::test]
+async fn empty_model_falls_back_to_default_model() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion("Hi");
+    let client = server
+        .client_builder()
+        .default_model(Model::GPT4(GPT4::GPT41Mini))
+        .build()
+        .unwrap();
+
+    let mut req = chat_request();
+    req.model = String::new();
+    client.chat_completion(req).await.unwrap();
+    client.chat_completion(chat_request()).await.unwrap();
+
+    let requests = server.received_requests();
+    assert_eq!(requests[0].json().unwrap()["model"], "gpt-4.1-mini");
+    assert_eq!(requests[1].json().unwrap()["model"], "gpt-4o");
+}
+
+#[tokio::test]
+async fn edit_via_chat_maps_request_and_response() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion("What day of the week is it?");
+    let client = server.client().unwrap();
+
+    let req =
+        EditRequest::new("gpt-4o".to_owned(), "Fix the spelling mistakes".to_owned())
+            .input("What day of the wek is it?".to_owned())
+            .temperature(0.5);
+    let response = client.edit_via_chat(req).await.unwrap();
+
+    assert_eq!(response.object, "edit");
+    assert_eq!(response.choices.len(), 1);
+    assert_eq!(response.choices[0].text, "What day of the week is it?");
+    assert_eq!(response.usage.total_tokens, 2);
+    let body = server.received_requests()[0].json().unwrap();
+    assert_eq!(body["model"], "gpt-4o");
+    assert_eq!(body["temperature"], 0.5);
+    assert_eq!(
+        body["messages"],
+        json!([
+            { "role": "system", "content": "Fix the spelling mistakes" },
+            { "role": "user", "content": "What day of the wek is it?" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn create_thread_and_run_posts_thread_and_run_settings() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(Method::POST, "/threads/runs", 200, run("queued"));
+    let client = server.client().unwrap();
+
+    let req = CreateThreadAndRunRequest::new("asst_abc123".to_owned())
+        .thread(CreateThreadRequest::with_user_message(
+            "What's the weather?",
+        ))
+        .instructions("Answer briefly.".to_owned())
+        .temperature(0.2);
+    let run = client.create_thread_and_run(req).await.unwrap();
+
+    assert_eq!(run.status, "queued");
+    let request = &server.received_requests()[0];
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, "/threads/runs");
+    assert_eq!(
+        request.json().unwrap(),
+        json!({
+            "assistant_id": "asst_abc123",
+            "thread": { "messages": [{ "role": "user", "content": "What's the weather?" }] },
+            "instructions": "Answer briefly.",
+            "temperature": 0.2
+        })
+    );
+}
+
+#[tokio::test]
+async fn file_list_page_sends_pagination_query() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::GET,
+        "/files",
+        200,
+        r#"{"object": "list", "data": []}"#,
+    );
+    let client = server.client().unwrap();
+
+    client
+        .file_list_page(Some(20), Some("asc".to_owned()), None, None)
+        .await
+        .unwrap();
+    client
+        .file_list_page(
+            Some(20),
+            None,
+            Some("file-abc123".to_owned()),
+            Some(FilePurpose::Batch),
+        )
+        .await
+        .unwrap();
+
+    let requests = server.received_requests();
+    assert_eq!(requests[0].path, "/files?limit=20&order=asc");
+    assert_eq!(
+        requests[1].path,
+        "/files?limit=20&after=file-abc123&purpose=batch"
+    );
+}
+
+#[tokio::test]
+async fn completion_meta_reads_request_id_and_rate_limits() {
+    let server = MockServer::start().await.unwrap();
+    let header = |name: &str, value: &str| (name.to_owned(), value.to_owned());
+    server.mock_with_headers(
+        Method::POST,
+        "/completions",
+        200,
+        vec![
+            header("x-request-id", "req_abc123"),
+            header("x-ratelimit-limit-requests", "60"),
+            header("x-ratelimit-limit-tokens", "150000"),
+            header("x-ratelimit-remaining-requests", "59"),
+            header("x-ratelimit-remaining-tokens", "149984"),
+            header("x-ratelimit-reset-requests", "1s"),
+            header("x-ratelimit-reset-tokens", "6m0s"),
+        ],
+        json!({
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 0,
+            "model": "gpt-3.5-turbo-instruct",
+            "choices": [{
+                "text": "Hi",
+                "index": 0,
+                "logprobs": null,
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 2, "completion_tokens": 1, "total_tokens": 3 }
+        })
+        .to_string(),
+    );
+    let client = server.client().unwrap();
+
+    let response = client.completion_meta("Say hi".into()).await.unwrap();
+
+    assert_eq!(response.data.choices[0].text, "Hi");
+    assert_eq!(response.request_id.as_deref(), Some("req_abc123"));
+    let rate_limit = response.rate_limit.unwrap();
+    assert_eq!(rate_limit.limit_requests, Some(60));
+    assert_eq!(rate_limit.limit_tokens, Some(150000));
+    assert_eq!(rate_limit.remaining_requests, Some(59));
+    assert_eq!(rate_limit.remaining_tokens, Some(149984));
+    assert_eq!(rate_limit.reset_requests, Some(Duration::from_secs(1)));
+    assert_eq!(rate_limit.reset_tokens, Some(Duration::from_secs(360)));
+}
+
+#[tokio::test]
+async fn chat_completion_meta_without_headers_has_no_metadata() {
+    let server = MockServer::start().await.unwrap();
+    server.mock_chat_completion("Hi");
+    let client = server.client().unwrap();
+
+    let response = client.chat_completion_meta(chat_request()).await.unwrap();
+
+    assert_eq!(response.data.get_choice(), "Hi");
+    assert!(response.request_id.is_none());
+    assert!(response.rate_limit.is_none());
+}
+
+#[tokio::test]
+async fn list_messages_sends_pagination_and_run_query() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::GET,
+        "/threads/thread_abc123/messages",
+        200,
+        r#"{"object": "list", "data": [], "first_id": "", "last_id": "", "has_more": false}"#,
+    );
+    let client = server.client().unwrap();
+
+    let messages = client
+        .list_messages(
+            "thread_abc123".to_owned(),
+            Some(10),
+            Some("desc".to_owned()),
+            Some("msg_abc123".to_owned()),
+            None,
+            Some("run_abc123".to_owned()),
+        )
+        .await
+        .unwrap();
+
+    assert!(messages.data.is_empty() && !messages.has_more);
+    let request = &server.received_requests()[0];
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.path,
+        "/threads/thread_abc123/messages?limit=10&order=desc&after=msg_abc123&run_id=run_abc123"
+    );
+}
+
+#[tokio::test]
+async fn delete_message_sends_delete_to_message_url() {
+    let server = MockServer::start().await.unwrap();
+    server.mock(
+        Method::DELETE,
+        "/threads/thread_abc123/messages/msg_abc123",
+        200,
+        json!({ "id": "msg_abc123", "object": "thread.message.deleted", "deleted": true })
+            .to_string(),
+    );
+    let client = server.client().unwrap();
+
+    let status = client
+        .delete_message("thread_abc123".to_owned(), "msg_abc123".to_owned())
+        .await
+        .unwrap();
+
+    assert!(status.deleted);
+    assert_eq!(status.id, "msg_abc123");
+    let request = &server.received_requests()[0];
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, "/threads/thread_abc123/messages/msg_abc123");
+}